@@ -5,6 +5,9 @@
 //! * `print` -- Prints the contents of the register, leaving it unchanged.
 //!
 //! The example also includes the `help` and `quit` built-in commands.
+//!
+//! When run with arguments, a single command is executed without prompting; e.g.,
+//! `cargo run --example calculator -- -c "add 1.5"`.
 
 use revolver::command;
 use revolver::command::Commander;
@@ -12,6 +15,7 @@ use revolver::command::NamedCommandParser;
 use revolver::looper::Looper;
 use revolver::terminal::{AccessTerminalError, Streaming, Terminal};
 use std::convert::Infallible;
+use std::process::ExitCode;
 
 #[derive(Debug, Default)]
 pub struct Register {
@@ -40,12 +44,12 @@ fn commander<T: Terminal>() -> Commander<Register, Infallible, T> {
     Commander::new(parsers)
 }
 
-fn main() {
+fn main() -> ExitCode {
     let mut terminal = Streaming::default();
     let commander = commander();
     let mut register = Register::default();
     let mut looper = Looper::new(&mut terminal, &commander, &mut register);
-    looper.run().unwrap();
+    looper.run_args(std::env::args().skip(1)).unwrap().exit_code()
 }
 
 mod add {
@@ -91,7 +95,7 @@ mod add {
             &self,
             s: &str,
         ) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
-            let value = f64::from_str(s).map_err(ParseCommandError::convert)?;
+            let value = f64::from_str(s).map_err(|err| ParseCommandError::convert(err).with_span(0..s.len()))?;
             Ok(Box::new(Add { value }))
         }
 
@@ -159,7 +163,7 @@ mod subtract {
             &self,
             s: &str,
         ) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
-            let value = f64::from_str(s).map_err(ParseCommandError::convert)?;
+            let value = f64::from_str(s).map_err(|err| ParseCommandError::convert(err).with_span(0..s.len()))?;
             Ok(Box::new(Subtract { value }))
         }
 
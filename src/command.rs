@@ -8,11 +8,12 @@ pub mod quit;
 pub use lint::*;
 
 use crate::looper::Looper;
-use crate::terminal::{AccessTerminalError, Terminal};
+use crate::terminal::{AccessTerminalError, Terminal, Value};
 use std::borrow::Cow;
 use std::collections::btree_map::Entry;
 use std::collections::BTreeMap;
 use std::fmt::{Debug, Display};
+use std::ops::Range;
 use thiserror::Error;
 
 /// Produced when a command could not executed.
@@ -75,6 +76,17 @@ pub enum ApplyOutcome {
     Skipped,
 }
 
+impl From<ApplyOutcome> for Value {
+    /// Converts the outcome to a string value: `applied` or `skipped`.
+    fn from(outcome: ApplyOutcome) -> Self {
+        match outcome {
+            ApplyOutcome::Applied => "applied",
+            ApplyOutcome::Skipped => "skipped",
+        }
+        .into()
+    }
+}
+
 /// A parser for constructing [`Command`] implementations from a text string (a line read from the
 /// terminal interface).
 pub trait NamedCommandParser<T> {
@@ -95,6 +107,13 @@ pub trait NamedCommandParser<T> {
     /// full command name.
     fn shorthand(&self) -> Option<Cow<'static, str>>;
 
+    /// Additional monikers for the command, beyond the [`shorthand`](NamedCommandParser::shorthand);
+    /// e.g., `ls` and `l` for a `list` command. The user may type in any of these strings instead of
+    /// the full command name. The default implementation returns no aliases.
+    fn aliases(&self) -> Vec<Cow<'static, str>> {
+        Vec::default()
+    }
+
     /// The (mandatory) complete name of the command. The user will type in the name of the command,
     /// followed by some (depending on the command) arguments.
     fn name(&self) -> Cow<'static, str>;
@@ -102,6 +121,57 @@ pub trait NamedCommandParser<T> {
     /// Describes the command. The description is displayed when invoking the `help` command.
     fn description(&self) -> Description;
 
+    /// Parses the given string slice along with a payload, which was supplied separately in a
+    /// heredoc block; e.g., the lines between `publish topic <<EOF` and `EOF`. The default
+    /// implementation rejects the payload.
+    ///
+    /// # Errors
+    /// [`ParseCommandError`] if the command couldn't be parsed.
+    #[allow(clippy::type_complexity)]
+    fn parse_with_payload(
+        &self,
+        s: &str,
+        payload: &str,
+    ) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        let _ = (s, payload);
+        Err(ParseCommandError::new(format!("'{}' does not accept a payload", self.name())))
+    }
+
+    /// Whether the command is destructive; i.e., its effects cannot be easily undone. The [`Looper`]
+    /// asks the user to confirm a destructive command before applying it, unless confirmations
+    /// are assumed (see [`Looper::with_assume_yes`]). The default implementation returns `false`.
+    fn is_destructive(&self) -> bool {
+        false
+    }
+
+    /// The category under which the command is listed by the `help` command; e.g., `Data`. Commands
+    /// are grouped by category in the order given by [`Commander::with_category_order`]. The default
+    /// implementation returns [`None`]; uncategorised commands are listed after the categorised ones.
+    fn category(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    /// Whether the command is hidden; i.e., omitted from the `help` listing (and the exported
+    /// command reference), while remaining parsable. The default implementation returns `false`.
+    fn is_hidden(&self) -> bool {
+        false
+    }
+
+    /// Whether the command is deprecated. A deprecated command remains parsable, but is marked as
+    /// such in the `help` listing, and the [`Looper`] prints a warning, pointing to the
+    /// [replacement](NamedCommandParser::replacement), whenever the command is run. The default
+    /// implementation returns `false`.
+    fn is_deprecated(&self) -> bool {
+        false
+    }
+
+    /// The command that replaces this (deprecated) command; e.g., `unmount` or `volume detach`.
+    /// The first word must identify another command that is not deprecated. The default
+    /// implementation returns [`None`].
+    fn replacement(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
     /// A convenience method for creating a [`Command`] object by invoking the given `ctor` closure,
     /// assuming that this command does not require any arguments.
     ///
@@ -121,9 +191,8 @@ pub trait NamedCommandParser<T> {
         if s.is_empty() {
             Ok(Box::new(ctor()))
         } else {
-            Err(ParseCommandError(
-                format!("invalid arguments to '{}': '{s}'", self.name()).into(),
-            ))
+            Err(ParseCommandError::new(format!("invalid arguments to '{}': '{s}'", self.name()))
+                .with_span(0..s.len()))
         }
     }
 }
@@ -170,10 +239,29 @@ impl Example {
 
 /// Decodes user input (typically a line read from a terminal interface) into a dynamic [`Command`] object, using
 /// a preconfigured map of parsers.
+///
+/// By default, the command identifier must match the name, shorthand or an alias of a command
+/// exactly. Matching may be relaxed to disregard case (see [`Commander::with_case_insensitive`])
+/// and to accept an unambiguous prefix of a command name (see [`Commander::with_prefix_matching`]).
 pub struct Commander<C, E, T> {
     parsers: Vec<Box<dyn NamedCommandParser<T, Context = C, Error = E>>>,
-    by_shorthand: BTreeMap<String, usize>,
+    by_alias: BTreeMap<String, usize>,
     by_name: BTreeMap<String, usize>,
+    category_order: Vec<Cow<'static, str>>,
+    case_insensitive: bool,
+    prefix_matching: bool,
+}
+
+/// The outcome of looking up the parser for a command identifier.
+enum Lookup {
+    /// The index of the matching parser.
+    Found(usize),
+
+    /// No parser matches.
+    Missing,
+
+    /// Several parsers match; the matching identifiers are given.
+    Ambiguous(Vec<String>),
 }
 
 impl<C, E, T> Commander<C, E, T> {
@@ -189,6 +277,51 @@ impl<C, E, T> Commander<C, E, T> {
     pub fn parsers(&self) -> impl Iterator<Item = &Box<dyn NamedCommandParser<T, Context = C, Error = E>>> {
         self.by_name.values().map(|&idx| &self.parsers[idx])
     }
+
+    /// Specifies the order in which categories are listed. Categories that are not in the given
+    /// order are listed after those that are, alphabetically.
+    #[must_use]
+    pub fn with_category_order<S: Into<Cow<'static, str>>>(mut self, order: impl IntoIterator<Item = S>) -> Self {
+        self.category_order = order.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Specifies whether command identifiers are matched without regard to case; e.g., `HELP`
+    /// matches `help`. An exact match is always preferred. By default, matching is case-sensitive.
+    #[must_use]
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Specifies whether a command identifier may be a prefix of a command name, provided that the
+    /// prefix is unambiguous; e.g., `sub` matches `subtract`, unless there is also a `submit`
+    /// command. Hidden commands are not matched by prefix. An exact match is always preferred. By
+    /// default, prefixes are not matched.
+    #[must_use]
+    pub fn with_prefix_matching(mut self, prefix_matching: bool) -> Self {
+        self.prefix_matching = prefix_matching;
+        self
+    }
+
+    /// The distinct categories of the underlying parsers that are not hidden, in listing order (see
+    /// [`Commander::with_category_order`]).
+    pub fn categories(&self) -> Vec<Cow<'static, str>> {
+        let mut categories = self
+            .parsers()
+            .filter(|parser| !parser.is_hidden())
+            .filter_map(|parser| parser.category())
+            .collect::<Vec<_>>();
+        categories.sort();
+        categories.dedup();
+        categories.sort_by_key(|category| {
+            self.category_order
+                .iter()
+                .position(|ordered| ordered == category)
+                .unwrap_or(self.category_order.len())
+        });
+        categories
+    }
 }
 
 /// Raised by [`Commander`] if there was something wrong with the parsers given to it. Perhaps
@@ -199,16 +332,60 @@ pub struct InvalidCommandParserSpec(String);
 
 /// Raised by either [`Commander`] or a [`NamedCommandParser`] if the supplied string slice could
 /// not be parsed into a valid [`Command`] object.
+///
+/// The error may optionally carry a byte span into the parsed string slice, identifying the offending
+/// part of the input. A [`NamedCommandParser`] specifies the span relative to the arguments it was
+/// given; [`Commander::parse`] adjusts the span to be relative to the complete input line.
+///
+/// An error may also signal that the input is `incomplete` rather than invalid; e.g., a JSON payload
+/// that has not yet been closed. The [`Looper`] then reads a continuation line, appending it to
+/// the input, instead of reporting the error.
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
-#[error("{0}")]
-pub struct ParseCommandError(pub Cow<'static, str>);
+#[error("{message}")]
+pub struct ParseCommandError {
+    pub message: Cow<'static, str>,
+    pub span: Option<Range<usize>>,
+    pub incomplete: bool,
+}
 
 impl ParseCommandError {
+    /// Creates a new [`ParseCommandError`] with the given message and no span.
+    pub fn new(message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            message: message.into(),
+            span: None,
+            incomplete: false,
+        }
+    }
+
+    /// Creates a new [`ParseCommandError`] signalling that the input is incomplete and more
+    /// input is needed.
+    pub fn incomplete(message: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            incomplete: true,
+            ..Self::new(message)
+        }
+    }
+
     /// Converts anything representable as a [`String`] into a [`ParseCommandError`], consuming
     /// the original. This is mostly used in error conversion; e.g., in [`Result::map_err()`].
     #[allow(clippy::needless_pass_by_value)]
     pub fn convert<E: ToString>(err: E) -> Self {
-        Self(err.to_string().into())
+        Self::new(err.to_string())
+    }
+
+    /// Sets the byte span of the offending input.
+    #[must_use]
+    pub fn with_span(mut self, span: Range<usize>) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// Shifts the span (if set) by the given number of bytes.
+    #[must_use]
+    pub fn offset(mut self, by: usize) -> Self {
+        self.span = self.span.map(|span| span.start + by..span.end + by);
+        self
     }
 }
 
@@ -251,7 +428,8 @@ impl<C, E, T> TryFrom<Vec<Box<dyn NamedCommandParser<T, Context = C , Error = E>
             )))
         }
 
-        let mut by_shorthand = BTreeMap::default();
+        // shorthands and aliases share a map, being alternatives to the command name
+        let mut by_alias = BTreeMap::default();
         let mut by_name = BTreeMap::default();
 
         for (index, parser) in parsers.iter().enumerate() {
@@ -268,7 +446,26 @@ impl<C, E, T> TryFrom<Vec<Box<dyn NamedCommandParser<T, Context = C , Error = E>
             if let Some(shorthand) = parser.shorthand() {
                 let shorthand = shorthand.into_owned();
                 check(&shorthand, &by_name)?;
-                insert(shorthand, index, &mut by_shorthand)?;
+                insert(shorthand, index, &mut by_alias)?;
+            }
+
+            for alias in parser.aliases() {
+                if alias.is_empty() || alias.contains(char::is_whitespace) {
+                    return Err(InvalidCommandParserSpec(format!(
+                        "invalid alias '{alias}' of command '{}': must be non-empty and free of whitespace",
+                        parser.name()
+                    )));
+                }
+                let alias = alias.into_owned();
+                check(&alias, &by_name)?;
+                insert(alias, index, &mut by_alias)?;
+            }
+
+            if parser.category().map_or(false, |category| category.trim().is_empty()) {
+                return Err(InvalidCommandParserSpec(format!(
+                    "invalid category of command '{}': must not be blank",
+                    parser.name()
+                )));
             }
 
             if parser.name().len() < 2 {
@@ -279,14 +476,39 @@ impl<C, E, T> TryFrom<Vec<Box<dyn NamedCommandParser<T, Context = C , Error = E>
             }
 
             let name = parser.name().into_owned();
-            check(&name, &by_shorthand)?;
+            check(&name, &by_alias)?;
             insert(name, index, &mut by_name)?;
         }
 
+        // check that each replacement identifies a command that is not deprecated itself
+        for parser in &parsers {
+            if let Some(replacement) = parser.replacement() {
+                let identifier = replacement.split(' ').next().unwrap_or_default();
+                match by_alias.get(identifier).or_else(|| by_name.get(identifier)) {
+                    None => {
+                        return Err(InvalidCommandParserSpec(format!(
+                            "unknown replacement '{replacement}' for command '{}'",
+                            parser.name()
+                        )))
+                    }
+                    Some(&index) if parsers[index].is_deprecated() => {
+                        return Err(InvalidCommandParserSpec(format!(
+                            "replacement '{replacement}' for command '{}' is deprecated",
+                            parser.name()
+                        )))
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
         Ok(Self {
             parsers,
-            by_shorthand,
+            by_alias,
             by_name,
+            category_order: Vec::default(),
+            case_insensitive: false,
+            prefix_matching: false,
         })
     }
 }
@@ -295,39 +517,111 @@ impl<C, E, T> Commander<C, E, T> {
     /// Parses the given string slice into a [`Command`] object.
     ///
     /// The input should be in the form `<command_identifier> [<command_args>]` where
-    /// `<command_identifier>` ∈ {`<command_name>`, `<command_shorthand>}`.
+    /// `<command_identifier>` ∈ {`<command_name>`, `<command_shorthand>`, `<command_alias>`}.
     ///
     /// # Errors
     /// [`ParseCommandError`] if a [`Command`] object could not be constructed.
     pub fn parse(&self, s: &str) -> Result<Box<dyn Command<T, Context = C , Error = E>>, ParseCommandError> {
+        self.dispatch(s, |parser, args| parser.parse(args))
+    }
+
+    /// Parses the given string slice into a [`Command`] object, as per [`Commander::parse`], passing
+    /// the given payload to [`NamedCommandParser::parse_with_payload`].
+    ///
+    /// # Errors
+    /// [`ParseCommandError`] if a [`Command`] object could not be constructed.
+    pub fn parse_with_payload(&self, s: &str, payload: &str) -> Result<Box<dyn Command<T, Context = C , Error = E>>, ParseCommandError> {
+        self.dispatch(s, |parser, args| parser.parse_with_payload(args, payload))
+    }
+
+    /// Looks up the parser for the command identifier at the start of `s`, and invokes `f` with the
+    /// parser and the remaining arguments, adjusting the span of any error to be relative to `s`.
+    #[allow(clippy::type_complexity)]
+    fn dispatch(
+        &self,
+        s: &str,
+        f: impl FnOnce(&dyn NamedCommandParser<T, Context = C, Error = E>, &str) -> Result<Box<dyn Command<T, Context = C , Error = E>>, ParseCommandError>,
+    ) -> Result<Box<dyn Command<T, Context = C , Error = E>>, ParseCommandError> {
         if s.is_empty() {
-            return Err(ParseCommandError("empty command string".into()));
+            return Err(ParseCommandError::new("empty command string"));
         }
 
         let index = s.find(' ').unwrap_or(s.len());
-        let name = &s[..index];
+        let identifier = &s[..index];
+        let parser = match self.lookup(identifier) {
+            Lookup::Found(index) => &*self.parsers[index],
+            Lookup::Missing => {
+                return Err(ParseCommandError::new(format!("no command parser for '{identifier}'")).with_span(0..index))
+            }
+            Lookup::Ambiguous(candidates) => {
+                let candidates = candidates.iter().map(|candidate| format!("'{candidate}'")).collect::<Vec<_>>();
+                return Err(ParseCommandError::new(format!(
+                    "ambiguous command '{identifier}'; could be any of {}",
+                    candidates.join(", ")
+                ))
+                .with_span(0..index));
+            }
+        };
 
-        let &parser_idx = self
-            .by_shorthand
-            .get(name)
-            .or_else(|| self.by_name.get(name))
-            .ok_or_else(|| ParseCommandError(format!("no command parser for '{name}'").into()))?;
+        let frag_offset = (index + 1).min(s.len());
+        f(parser, &s[frag_offset..]).map_err(|err| err.offset(frag_offset))
+    }
 
-        let command_frag = if index == s.len() {
-            ""
-        } else {
-            &s[index + 1..]
-        };
-        self.parsers[parser_idx].parse(command_frag)
+    /// Looks up the parser for the command identifier at the start of the given string slice; i.e.,
+    /// the parser that [`Commander::parse`] would delegate to. Returns [`None`] if no parser matches.
+    pub fn parser_for(&self, s: &str) -> Option<&dyn NamedCommandParser<T, Context = C, Error = E>> {
+        match self.lookup(&s[..s.find(' ').unwrap_or(s.len())]) {
+            Lookup::Found(index) => Some(&*self.parsers[index]),
+            Lookup::Missing | Lookup::Ambiguous(_) => None,
+        }
     }
-}
 
-pub(crate) fn read_command<C, E, T: Terminal>(
-    looper: &mut Looper<C, E, T>,
-    prompt: &str,
-) -> Result<Box<dyn Command<T, Context = C , Error = E>>, AccessTerminalError> {
-    let (terminal, commander, _) = looper.split();
-    terminal.read_value(prompt, |str| commander.parse(str))
+    /// Looks up the parser for the given command identifier: first by an exact match, then (if
+    /// enabled) by a case-insensitive match, and lastly (if enabled) by a prefix of a name.
+    fn lookup(&self, identifier: &str) -> Lookup {
+        if let Some(&index) = self.by_alias.get(identifier).or_else(|| self.by_name.get(identifier)) {
+            return Lookup::Found(index);
+        }
+        if identifier.is_empty() {
+            return Lookup::Missing;
+        }
+
+        if self.case_insensitive {
+            let lowercase = identifier.to_lowercase();
+            let matches = self
+                .by_alias
+                .iter()
+                .chain(&self.by_name)
+                .filter(|(key, _)| key.to_lowercase() == lowercase);
+            match Self::unique(matches) {
+                Lookup::Missing => {}
+                lookup => return lookup,
+            }
+        }
+
+        if self.prefix_matching {
+            let lowercase = identifier.to_lowercase();
+            let matches = self
+                .by_name
+                .iter()
+                .filter(|(_, &index)| !self.parsers[index].is_hidden())
+                .filter(|(key, _)| {
+                    key.starts_with(identifier) || self.case_insensitive && key.to_lowercase().starts_with(&lowercase)
+                });
+            return Self::unique(matches);
+        }
+        Lookup::Missing
+    }
+
+    /// Reduces the matching entries to a single parser, if they all map to the same one.
+    fn unique<'k>(matches: impl Iterator<Item = (&'k String, &'k usize)>) -> Lookup {
+        let matches = matches.collect::<Vec<_>>();
+        match matches.first() {
+            None => Lookup::Missing,
+            Some(&(_, &index)) if matches.iter().all(|&(_, &other)| other == index) => Lookup::Found(index),
+            Some(_) => Lookup::Ambiguous(matches.into_iter().map(|(key, _)| key.clone()).collect()),
+        }
+    }
 }
 
 #[cfg(test)]
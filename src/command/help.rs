@@ -1,13 +1,19 @@
-//! A self-help guide, outlining the available commands and how to use them.
+//! A self-help guide, outlining the available commands and how to use them. Commands are grouped
+//! by category (see [`NamedCommandParser::category`]), if any are categorised. The command
+//! reference may also be exported as Markdown, a man page or JSON (see [`HelpFormat`]).
+
+mod export;
+
+pub use export::*;
 
 use crate::command::{
-    ApplyCommandError, ApplyOutcome, Command, Commander, Description, NamedCommandParser,
+    ApplyCommandError, ApplyOutcome, Command, Commander, Description, Example, NamedCommandParser,
     ParseCommandError,
 };
 use crate::looper::Looper;
-use crate::terminal::{AccessTerminalError, Terminal};
+use crate::terminal::{AccessTerminalError, Colour, OutputFormat, Report, Style, Terminal};
 use stanza::renderer::console::{Console, Decor};
-use stanza::renderer::Renderer;
+use stanza::renderer::{wrap, Renderer};
 use stanza::style::{Bold, Header, MaxWidth, MinWidth, Palette16, Styles, TextFg};
 use stanza::table::{Cell, Col, Row, Table};
 use std::borrow::{Borrow, Cow};
@@ -16,16 +22,50 @@ use std::marker::PhantomData;
 /// The `help` command. The list of available commands is obtained by interrogating the [`Commander`]. The output
 /// of the help command is a rendered [Stanza](https://github.com/obsidiandynamics/stanza) table, enumerating
 /// each of the available commands, their name (incl. shorthand, if set) and description (incl. any examples).
+/// Hidden commands are omitted, and deprecated commands are marked as such.
+///
+/// Alternatively, the command reference is printed in the given [`HelpFormat`]. In a machine-readable
+/// output format (see [`Terminal::output_format`]), it is printed as JSON by default; in the CSV
+/// format, each command is emitted as a CSV record with the fields of its JSON object.
+///
+/// If a category is given, only the commands in that category are listed.
 pub struct Help<C, E> {
+    format: Option<HelpFormat>,
+    title: Cow<'static, str>,
+    category: Option<String>,
     __phantom_data: PhantomData<(C, E)>
 }
 
 impl<C, E> Default for Help<C, E> {
     fn default() -> Self {
         Self {
-            __phantom_data: PhantomData::default(),
+            format: None,
+            title: DEFAULT_TITLE.into(),
+            category: None,
+            __phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<C, E> Help<C, E> {
+    /// Creates a [`Help`] command that prints the command reference in the given format, using
+    /// the given title for exported documents.
+    pub fn new(format: Option<HelpFormat>, title: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            format,
+            title: title.into(),
+            category: None,
+            __phantom_data: PhantomData,
         }
     }
+
+    /// Restricts the listing to the commands in the given category (matched without regard to
+    /// case).
+    #[must_use]
+    pub fn with_category(mut self, category: impl Into<String>) -> Self {
+        self.category = Some(category.into());
+        self
+    }
 }
 
 impl<C, E, T: Terminal> Command<T> for Help<C, E> {
@@ -37,30 +77,164 @@ impl<C, E, T: Terminal> Command<T> for Help<C, E> {
         looper: &mut Looper<C, E, T>,
     ) -> Result<ApplyOutcome, ApplyCommandError<E>> {
         let (terminal, commander, _) = looper.split();
-        print_commands(commander, terminal)?;
+        let session_format = terminal.output_format();
+        let format = self.format.unwrap_or(if session_format.is_machine_readable() {
+            HelpFormat::Json
+        } else {
+            HelpFormat::Text
+        });
+        let mut groups = groups(commander);
+        if let Some(category) = &self.category {
+            groups.retain(|group| {
+                group
+                    .heading
+                    .as_ref()
+                    .map_or(false, |heading| heading.eq_ignore_ascii_case(category))
+            });
+            if groups.is_empty() {
+                unknown_category(commander, category).print(terminal)?;
+                return Ok(ApplyOutcome::Skipped);
+            }
+        }
+        match format {
+            HelpFormat::Text => print_commands(commander, &groups, terminal)?,
+            HelpFormat::Markdown => terminal.print(&render_markdown(&groups, &self.title))?,
+            HelpFormat::Man => terminal.print(&render_man_page(&groups, &self.title))?,
+            HelpFormat::Json if self.format.is_none() && session_format == OutputFormat::Csv => {
+                for command in command_values(&groups) {
+                    terminal.emit(&command)?;
+                }
+            }
+            HelpFormat::Json => terminal.print_line(&render_json(&groups).to_json())?,
+        }
         Ok(ApplyOutcome::Applied)
     }
 }
 
-/// Parser for [`Help`].
+/// Reports a category that matches none of the commands, listing the known categories (if any)
+/// in the hint.
+fn unknown_category<C, E, T>(commander: &Commander<C, E, T>, category: &str) -> Report {
+    let report = Report::new("Invalid input", format!("no such category '{category}'"));
+    let headings = groups(commander)
+        .into_iter()
+        .filter_map(|group| group.heading)
+        .collect::<Vec<_>>();
+    if headings.is_empty() {
+        report.with_hint("no categories are defined")
+    } else {
+        report.with_hint(format!("expected one of: {}", headings.join(", ")))
+    }
+}
+
+/// The default title of exported documents.
+const DEFAULT_TITLE: &str = "Commands";
+
+/// The heading under which uncategorised commands are listed, when other commands are categorised.
+const UNCATEGORISED: &str = "Other";
+
+/// Commands listed under a common heading.
+struct Group<'c, C, E, T> {
+    /// The category of the commands, or [`UNCATEGORISED`]; [`None`] if no command is categorised.
+    heading: Option<Cow<'static, str>>,
+    parsers: Vec<&'c dyn NamedCommandParser<T, Context = C, Error = E>>,
+}
+
+/// Groups the commands that are not hidden by category, in listing order, followed by the
+/// uncategorised commands. If no command is categorised, a single group without a heading is
+/// returned.
+fn groups<C, E, T>(commander: &Commander<C, E, T>) -> Vec<Group<'_, C, E, T>> {
+    let in_category = |category: Option<&Cow<'static, str>>| {
+        commander
+            .parsers()
+            .filter(|parser| !parser.is_hidden() && parser.category().as_ref() == category)
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+    };
+    let categories = commander.categories();
+    if categories.is_empty() {
+        return vec![Group {
+            heading: None,
+            parsers: in_category(None),
+        }];
+    }
+
+    let mut groups = categories
+        .into_iter()
+        .map(|category| Group {
+            parsers: in_category(Some(&category)),
+            heading: Some(category),
+        })
+        .collect::<Vec<_>>();
+    let uncategorised = in_category(None);
+    if !uncategorised.is_empty() {
+        groups.push(Group {
+            heading: Some(UNCATEGORISED.into()),
+            parsers: uncategorised,
+        });
+    }
+    groups
+}
+
+/// Parser for [`Help`]. Accepts an optional category, followed by an optional `--format <format>`
+/// argument (or `--format=<format>`), where the format is parsed by
+/// [`HelpFormat::from_str`](std::str::FromStr::from_str).
 pub struct Parser<C, E> {
+    title: Cow<'static, str>,
     __phantom_data: PhantomData<(C, E)>
 }
 
 impl<C, E> Default for Parser<C, E> {
     fn default() -> Self {
         Self {
-            __phantom_data: PhantomData::default(),
+            title: DEFAULT_TITLE.into(),
+            __phantom_data: PhantomData,
         }
     }
 }
 
+impl<C, E> Parser<C, E> {
+    /// Specifies the title of exported documents; e.g., the name of the application. By default,
+    /// it is `Commands`.
+    #[must_use]
+    pub fn with_title(mut self, title: impl Into<Cow<'static, str>>) -> Self {
+        self.title = title.into();
+        self
+    }
+}
+
 impl<C: 'static, E: 'static, T: Terminal> NamedCommandParser<T> for Parser<C, E> {
     type Context = C;
     type Error = E;
 
     fn parse(&self, s: &str) -> Result<Box<dyn Command<T, Context = C , Error = E>>, ParseCommandError> {
-        self.parse_no_args(s, Help::default)
+        let (category, option) = s.find("--").map_or((s, ""), |index| s.split_at(index));
+        let format = if option.is_empty() {
+            None
+        } else {
+            let offset = s.len() - option.len();
+            let invalid = || {
+                ParseCommandError::new(format!("invalid arguments to '{}': '{option}'", <Self as NamedCommandParser<T>>::name(self)))
+                    .with_span(offset..s.len())
+            };
+            let value = match option.strip_prefix("--format") {
+                Some(rest) if rest.starts_with('=') => &rest[1..],
+                Some(rest) if rest.starts_with(char::is_whitespace) => rest.trim_start(),
+                _ => return Err(invalid()),
+            };
+            let offset = s.len() - value.len();
+            let format = value
+                .parse()
+                .map_err(|err| ParseCommandError::new(err).with_span(offset..s.len()))?;
+            Some(format)
+        };
+
+        let help = Help::new(format, self.title.clone());
+        let category = category.trim();
+        if category.is_empty() {
+            Ok(Box::new(help))
+        } else {
+            Ok(Box::new(help.with_category(category)))
+        }
     }
 
     fn shorthand(&self) -> Option<Cow<'static, str>> {
@@ -74,51 +248,124 @@ impl<C: 'static, E: 'static, T: Terminal> NamedCommandParser<T> for Parser<C, E>
     fn description(&self) -> Description {
         Description {
             purpose: "Displays a list of commands, their usage syntax and examples.".into(),
-            usage: Cow::default(),
-            examples: Vec::default(),
+            usage: "[<category>] [--format <text|markdown|man|json>]".into(),
+            examples: vec![Example {
+                scenario: "export the list of commands as Markdown".into(),
+                command: "--format markdown".into(),
+            }],
+        }
+    }
+}
+
+/// The minimum width of the command column.
+const MIN_COMMAND_WIDTH: usize = 15;
+
+/// The narrowest description column that is rendered alongside the command column; on a narrower
+/// terminal, the description is stacked beneath the command instead.
+const MIN_DESCRIPTION_WIDTH: usize = 40;
+
+/// The widest description column, beyond which lines are wrapped for readability.
+const MAX_DESCRIPTION_WIDTH: usize = 120;
+
+/// The indentation of the description in the stacked layout.
+const STACKED_INDENT: &str = "    ";
+
+/// The alternatives to the name of the command: its shorthand (if set), followed by its aliases.
+fn monikers<C, E, T>(parser: &dyn NamedCommandParser<T, Context = C, Error = E>) -> Vec<Cow<'static, str>> {
+    parser.shorthand().into_iter().chain(parser.aliases()).collect()
+}
+
+fn label<C, E, T: Terminal>(parser: &dyn NamedCommandParser<T, Context = C, Error = E>) -> String {
+    let mut label = String::new();
+    for moniker in monikers(parser) {
+        label.push_str(moniker.borrow());
+        label.push_str(", ");
+    }
+    label.push_str(&parser.name());
+    label
+}
+
+fn describe<C, E, T: Terminal>(parser: &dyn NamedCommandParser<T, Context = C, Error = E>) -> String {
+    let description = parser.description();
+    let mut desc_buf = String::new();
+    desc_buf.push_str(&format!("{}\n", description.purpose));
+    if parser.is_deprecated() {
+        desc_buf.push_str("deprecated");
+        if let Some(replacement) = parser.replacement() {
+            desc_buf.push_str(" - use '");
+            desc_buf.push_str(&replacement);
+            desc_buf.push_str("' instead");
         }
+        desc_buf.push('\n');
     }
+    desc_buf.push_str(&format!("usage: {} {}\n", parser.name(), description.usage));
+    for example in &description.examples {
+        desc_buf.push_str(&format!("example - {}:\n", example.scenario));
+        desc_buf.push_str(&format!("    {} {}\n", parser.name(), example.command));
+    }
+    desc_buf
+}
+
+/// The width of the command column for the given commander. Hidden commands are not listed, so
+/// they do not count.
+fn command_width<C, E, T: Terminal>(commander: &Commander<C, E, T>) -> usize {
+    commander
+        .parsers()
+        .filter(|parser| !parser.is_hidden())
+        .map(|parser| label(parser.as_ref()).chars().count())
+        .fold(MIN_COMMAND_WIDTH, usize::max)
+}
+
+/// The width of the description column when rendered alongside the command column on a
+/// terminal of the given width, or [`None`] if the terminal is too narrow for it.
+fn description_width<C, E, T: Terminal>(commander: &Commander<C, E, T>, columns: usize) -> Option<usize> {
+    let width = columns.saturating_sub(command_width(commander) + 1);
+    (width >= MIN_DESCRIPTION_WIDTH).then_some(width.min(MAX_DESCRIPTION_WIDTH))
 }
 
-fn commands<C, E, T: Terminal>(commander: &Commander<C, E, T>) -> Table {
-    let mut table = Table::default()
+/// Renders the heading of a group of commands on a line of its own.
+fn heading_line(heading: &str, ansi: bool) -> String {
+    let mut line = format!("{heading}:");
+    if ansi {
+        line = Style::default().fg(Colour::Yellow).bold().paint(&line);
+    }
+    line.push('\n');
+    line
+}
+
+/// Tabulates the commands of the given group. The column headers are omitted for a group with a
+/// heading.
+fn commands<C, E, T: Terminal>(commander: &Commander<C, E, T>, group: &Group<C, E, T>, description_width: usize) -> Table {
+    let table = Table::default()
         .with_cols(vec![
-            Col::new(Styles::default().with(MinWidth(15))),
-            Col::new(Styles::default().with(MinWidth(65)).with(MaxWidth(120))),
-        ])
-        .with_row(Row::new(
+            Col::new(Styles::default().with(MinWidth(command_width(commander)))),
+            Col::new(
+                Styles::default()
+                    .with(MinWidth(description_width))
+                    .with(MaxWidth(description_width)),
+            ),
+        ]);
+    let mut table = if group.heading.is_some() {
+        table
+    } else {
+        table.with_row(Row::new(
             Styles::default()
                 .with(Header(true))
                 .with(Bold(true))
                 .with(TextFg(Palette16::Yellow)),
             vec!["Command".into(), "Description".into()],
-        ));
-
-    for parser in commander.parsers() {
-        let mut command = String::new();
-        if let Some(shorthand) = parser.shorthand() {
-            command.push_str(shorthand.borrow());
-            command.push_str(", ");
-        }
-        command.push_str(&parser.name());
-
-        let description = parser.description();
-        let mut desc_buf = String::new();
-        desc_buf.push_str(&format!("{}\n", description.purpose));
-        desc_buf.push_str(&format!("usage: {} {}\n", parser.name(), description.usage));
-        for example in &description.examples {
-            desc_buf.push_str(&format!("example - {}:\n", example.scenario));
-            desc_buf.push_str(&format!("    {} {}\n", parser.name(), example.command));
-        }
+        ))
+    };
 
+    for &parser in &group.parsers {
         table.push_row(Row::new(
             Styles::default(),
             vec![
                 Cell::new(
                     Styles::default().with(TextFg(Palette16::BrightGreen)),
-                    command.into(),
+                    label(parser).into(),
                 ),
-                Cell::new(Styles::default().with(Bold(true)), desc_buf.into()),
+                Cell::new(Styles::default().with(Bold(true)), describe(parser).into()),
             ],
         ));
     }
@@ -126,16 +373,60 @@ fn commands<C, E, T: Terminal>(commander: &Commander<C, E, T>) -> Table {
     table
 }
 
+/// Renders the commands one beneath the other, with each description indented and wrapped to
+/// the given width; used on terminals that are too narrow for a table.
+fn stacked_commands<C, E, T: Terminal>(groups: &[Group<C, E, T>], columns: usize, ansi: bool) -> String {
+    let width = columns.saturating_sub(STACKED_INDENT.len()).max(1);
+    let label_style = Style::default().fg(Colour::BrightGreen);
+    let mut buf = String::new();
+    for group in groups {
+        if let Some(heading) = &group.heading {
+            buf.push_str(&heading_line(heading, ansi));
+            buf.push('\n');
+        }
+        for &parser in &group.parsers {
+            let label = label(parser);
+            buf.push_str(&if ansi { label_style.paint(&label) } else { label });
+            buf.push('\n');
+            for line in wrap(&describe(parser), width) {
+                buf.push_str(STACKED_INDENT);
+                buf.push_str(line.trim_end());
+                buf.push('\n');
+            }
+            buf.push('\n');
+        }
+    }
+    buf
+}
+
 fn print_commands<C, E, T: Terminal>(
     commander: &Commander<C, E, T>,
+    groups: &[Group<C, E, T>],
     terminal: &mut T,
 ) -> Result<(), AccessTerminalError> {
-    let renderer = Console(
-        Decor::default()
-            .suppress_all_lines()
-            .suppress_outer_border(),
-    );
-    terminal.print_line(&renderer.render(&commands(commander)))
+    let columns = terminal.size().columns;
+    let ansi = terminal.supports_ansi();
+    let Some(description_width) = description_width(commander, columns) else {
+        return terminal.page(&stacked_commands(groups, columns, ansi));
+    };
+
+    let mut decor = Decor::default()
+        .suppress_all_lines()
+        .suppress_outer_border();
+    if !ansi {
+        decor = decor.suppress_escape_codes();
+    }
+    let renderer = Console(decor);
+    let mut buf = String::new();
+    for group in groups {
+        // each row ends with a blank line, which also separates the groups
+        if let Some(heading) = &group.heading {
+            buf.push_str(&heading_line(heading, ansi));
+        }
+        buf.push_str(&renderer.render(&commands(commander, group, description_width)));
+        buf.push('\n');
+    }
+    terminal.page(&buf)
 }
 
 #[cfg(test)]
@@ -0,0 +1,252 @@
+//! Exports of the command reference for publishing outside the terminal; e.g., operator docs.
+//! Each export enumerates the commands of a [`Commander`] (other than hidden ones), along with
+//! their descriptions and examples, grouped and ordered as in the `help` listing.
+
+use crate::command::help::{groups, monikers, Group};
+use crate::command::{Commander, NamedCommandParser};
+use crate::terminal::{Terminal, Value};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The format of the `help` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpFormat {
+    /// A table, fitted to the terminal.
+    Text,
+
+    /// A Markdown document (see [`markdown`]).
+    Markdown,
+
+    /// A roff man page (see [`man_page`]).
+    Man,
+
+    /// A JSON document (see [`json`]).
+    Json,
+}
+
+impl Display for HelpFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Text => "text",
+            Self::Markdown => "markdown",
+            Self::Man => "man",
+            Self::Json => "json",
+        })
+    }
+}
+
+impl FromStr for HelpFormat {
+    type Err = String;
+
+    /// Parses `text`, `markdown` (or `md`), `man` (or `roff`) or `json`, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "markdown" | "md" => Ok(Self::Markdown),
+            "man" | "roff" => Ok(Self::Man),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unsupported help format '{s}'; expected one of text, markdown, man or json")),
+        }
+    }
+}
+
+/// The full usage syntax of a command: its name, followed by the syntax of its arguments, if any.
+fn usage<C, E, T>(parser: &dyn NamedCommandParser<T, Context = C, Error = E>) -> String {
+    let usage = parser.description().usage;
+    if usage.is_empty() {
+        parser.name().into_owned()
+    } else {
+        format!("{} {usage}", parser.name())
+    }
+}
+
+/// Renders the command reference as a Markdown document, with the given title as the top-level
+/// heading, and a section for each command. If the commands are categorised, each category is a
+/// section of its own, containing the sections of its commands.
+pub fn markdown<C, E, T: Terminal>(commander: &Commander<C, E, T>, title: &str) -> String {
+    render_markdown(&groups(commander), title)
+}
+
+pub(super) fn render_markdown<C, E, T>(groups: &[Group<C, E, T>], title: &str) -> String {
+    let mut buf = format!("# {title}\n");
+    for group in groups {
+        let command_level = if let Some(heading) = &group.heading {
+            buf.push_str("\n## ");
+            buf.push_str(heading);
+            buf.push('\n');
+            "\n### `"
+        } else {
+            "\n## `"
+        };
+        for &parser in &group.parsers {
+            markdown_command(parser, command_level, &mut buf);
+        }
+    }
+    buf
+}
+
+fn markdown_command<C, E, T>(
+    parser: &dyn NamedCommandParser<T, Context = C, Error = E>,
+    command_level: &str,
+    buf: &mut String,
+) {
+    let description = parser.description();
+    buf.push_str(command_level);
+    buf.push_str(&parser.name());
+    buf.push('`');
+    let monikers = monikers(parser);
+    if !monikers.is_empty() {
+        buf.push_str(" (`");
+        buf.push_str(&monikers.join("`, `"));
+        buf.push_str("`)");
+    }
+    buf.push_str("\n\n");
+    buf.push_str(&description.purpose);
+    if parser.is_deprecated() {
+        match parser.replacement() {
+            Some(replacement) => {
+                buf.push_str("\n\n**Deprecated:** use `");
+                buf.push_str(&replacement);
+                buf.push_str("` instead.");
+            }
+            None => buf.push_str("\n\n**Deprecated.**"),
+        }
+    }
+    buf.push_str("\n\n**Usage:** `");
+    buf.push_str(&usage(parser));
+    buf.push_str("`\n");
+    if !description.examples.is_empty() {
+        buf.push_str("\n**Examples:**\n\n");
+        for example in &description.examples {
+            buf.push_str("* ");
+            buf.push_str(&example.scenario);
+            buf.push_str(": `");
+            buf.push_str(&parser.name());
+            buf.push(' ');
+            buf.push_str(&example.command);
+            buf.push_str("`\n");
+        }
+    }
+}
+
+/// Escapes text for inclusion in a roff document: backslashes are escaped, as are control
+/// characters (`.` and `'`) at the start of a line.
+fn roff_escape(s: &str) -> String {
+    s.lines()
+        .map(|line| {
+            let line = line.replace('\\', "\\e");
+            if line.starts_with(['.', '\'']) {
+                format!("\\&{line}")
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders the command reference as a roff man page (in section 7), with the given title as the
+/// name of the page, and a subsection for each command. If the commands are categorised, each
+/// category is a section of its own; otherwise, the commands are in the `COMMANDS` section.
+pub fn man_page<C, E, T: Terminal>(commander: &Commander<C, E, T>, title: &str) -> String {
+    render_man_page(&groups(commander), title)
+}
+
+pub(super) fn render_man_page<C, E, T>(groups: &[Group<C, E, T>], title: &str) -> String {
+    let title = roff_escape(title);
+    let mut buf = format!(
+        ".TH \"{}\" 7\n.SH NAME\n{title} \\- command reference\n",
+        title.to_uppercase()
+    );
+    for group in groups {
+        buf.push_str(".SH ");
+        match &group.heading {
+            Some(heading) => buf.push_str(&roff_escape(&heading.to_uppercase())),
+            None => buf.push_str("COMMANDS"),
+        }
+        buf.push('\n');
+        for &parser in &group.parsers {
+            man_page_command(parser, &mut buf);
+        }
+    }
+    buf
+}
+
+fn man_page_command<C, E, T>(parser: &dyn NamedCommandParser<T, Context = C, Error = E>, buf: &mut String) {
+    let description = parser.description();
+    buf.push_str(".SS ");
+    for moniker in monikers(parser) {
+        buf.push_str(&roff_escape(&moniker));
+        buf.push_str(", ");
+    }
+    buf.push_str(&roff_escape(&parser.name()));
+    buf.push('\n');
+    buf.push_str(&roff_escape(&description.purpose));
+    if parser.is_deprecated() {
+        buf.push_str("\n.PP\nDeprecated");
+        if let Some(replacement) = parser.replacement() {
+            buf.push_str("; use \\fB");
+            buf.push_str(&roff_escape(&replacement));
+            buf.push_str("\\fR instead");
+        }
+        buf.push('.');
+    }
+    buf.push_str("\n.PP\nUsage:\n.RS\n.nf\n");
+    buf.push_str(&roff_escape(&usage(parser)));
+    buf.push_str("\n.fi\n.RE\n");
+    for example in &description.examples {
+        buf.push_str(".PP\nExample \\- ");
+        buf.push_str(&roff_escape(&example.scenario));
+        buf.push_str(":\n.RS\n.nf\n");
+        buf.push_str(&roff_escape(&format!("{} {}", parser.name(), example.command)));
+        buf.push_str("\n.fi\n.RE\n");
+    }
+}
+
+/// Renders the command reference as a JSON document: an object with a `commands` array, wherein
+/// each command is an object with the members `name`, `shorthand` (or `null`), `aliases`,
+/// `category` (or `null`), `purpose`, `deprecated`, `replacement` (or `null`), `usage` (the full
+/// usage syntax, including the name) and `examples` (objects with a `scenario` and a full
+/// `command`).
+pub fn json<C, E, T: Terminal>(commander: &Commander<C, E, T>) -> Value {
+    render_json(&groups(commander))
+}
+
+pub(super) fn render_json<C, E, T>(groups: &[Group<C, E, T>]) -> Value {
+    Value::object([("commands", Value::Array(command_values(groups)))])
+}
+
+/// Describes each of the commands in the given groups as a JSON object.
+pub(super) fn command_values<C, E, T>(groups: &[Group<C, E, T>]) -> Vec<Value> {
+    groups
+        .iter()
+        .flat_map(|group| &group.parsers)
+        .map(|&parser| {
+            let description = parser.description();
+            let examples = description
+                .examples
+                .iter()
+                .map(|example| {
+                    Value::object([
+                        ("scenario", example.scenario.clone().into()),
+                        ("command", format!("{} {}", parser.name(), example.command).into()),
+                    ])
+                })
+                .collect::<Vec<_>>();
+            Value::object([
+                ("name", parser.name().into()),
+                ("shorthand", parser.shorthand().into()),
+                ("aliases", parser.aliases().into()),
+                ("category", parser.category().into()),
+                ("purpose", description.purpose.into()),
+                ("deprecated", parser.is_deprecated().into()),
+                ("replacement", parser.replacement().into()),
+                ("usage", usage(parser).into()),
+                ("examples", Value::Array(examples)),
+            ])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,262 @@
+// $coverage:ignore-start
+
+use crate::command::help::export::roff_escape;
+use crate::command::help::{json, man_page, markdown, HelpFormat, Parser};
+use crate::command::{quit, ApplyCommandError, ApplyOutcome, Command, Commander, Description, NamedCommandParser, ParseCommandError};
+use crate::looper::Looper;
+use crate::terminal::{Mock, Terminal};
+use std::borrow::Cow;
+use std::convert::Infallible;
+
+fn commander() -> Commander<(), Infallible, Mock<'static>> {
+    Commander::new(vec![Box::new(Parser::default()), Box::new(quit::Parser::default())])
+}
+
+#[test]
+fn help_format_from_str() {
+    assert_eq!(Ok(HelpFormat::Text), "text".parse());
+    assert_eq!(Ok(HelpFormat::Markdown), "markdown".parse());
+    assert_eq!(Ok(HelpFormat::Markdown), "MD".parse());
+    assert_eq!(Ok(HelpFormat::Man), "man".parse());
+    assert_eq!(Ok(HelpFormat::Man), "roff".parse());
+    assert_eq!(Ok(HelpFormat::Json), "json".parse());
+    assert_eq!(
+        Err("unsupported help format 'html'; expected one of text, markdown, man or json".to_string()),
+        "html".parse::<HelpFormat>()
+    );
+    for format in [HelpFormat::Text, HelpFormat::Markdown, HelpFormat::Man, HelpFormat::Json] {
+        assert_eq!(Ok(format), format.to_string().parse());
+    }
+}
+
+#[test]
+fn markdown_content() {
+    assert_eq!("\
+# Reactor console
+
+## `help` (`h`)
+
+Displays a list of commands, their usage syntax and examples.
+
+**Usage:** `help [<category>] [--format <text|markdown|man|json>]`
+
+**Examples:**
+
+* export the list of commands as Markdown: `help --format markdown`
+
+## `quit` (`q`)
+
+Exits the program, optionally with the given status code.
+
+**Usage:** `quit [<code>]`
+
+**Examples:**
+
+* exit with status code 2: `quit 2`
+", markdown(&commander(), "Reactor console"));
+}
+
+#[test]
+fn man_page_content() {
+    assert_eq!(r#".TH "REACTOR" 7
+.SH NAME
+reactor \- command reference
+.SH COMMANDS
+.SS h, help
+Displays a list of commands, their usage syntax and examples.
+.PP
+Usage:
+.RS
+.nf
+help [<category>] [--format <text|markdown|man|json>]
+.fi
+.RE
+.PP
+Example \- export the list of commands as Markdown:
+.RS
+.nf
+help --format markdown
+.fi
+.RE
+.SS q, quit
+Exits the program, optionally with the given status code.
+.PP
+Usage:
+.RS
+.nf
+quit [<code>]
+.fi
+.RE
+.PP
+Example \- exit with status code 2:
+.RS
+.nf
+quit 2
+.fi
+.RE
+"#, man_page(&commander(), "reactor"));
+}
+
+#[test]
+fn roff_escape_control_characters() {
+    assert_eq!("plain", roff_escape("plain"));
+    assert_eq!("a \\e b", roff_escape("a \\ b"));
+    assert_eq!("\\&.TH\nnext\n\\&'quoted", roff_escape(".TH\nnext\n'quoted"));
+}
+
+#[test]
+fn json_content() {
+    assert_eq!(
+        concat!(
+            r#"{"commands":["#,
+            r#"{"name":"help","shorthand":"h","aliases":[],"category":null,"purpose":"Displays a list of commands, their usage syntax and examples.","#,
+            r#""deprecated":false,"replacement":null,"#,
+            r#""usage":"help [<category>] [--format <text|markdown|man|json>]","#,
+            r#""examples":[{"scenario":"export the list of commands as Markdown","command":"help --format markdown"}]},"#,
+            r#"{"name":"quit","shorthand":"q","aliases":[],"category":null,"purpose":"Exits the program, optionally with the given status code.","#,
+            r#""deprecated":false,"replacement":null,"#,
+            r#""usage":"quit [<code>]","#,
+            r#""examples":[{"scenario":"exit with status code 2","command":"quit 2"}]}"#,
+            r#"]}"#
+        ),
+        json(&commander()).to_json()
+    );
+}
+
+struct StatusCommand;
+
+impl<T: Terminal> Command<T> for StatusCommand {
+    type Context = ();
+    type Error = Infallible;
+
+    fn apply(&mut self, _: &mut Looper<(), Infallible, T>) -> Result<ApplyOutcome, ApplyCommandError<Infallible>> {
+        unimplemented!()
+    }
+}
+
+struct StatusParser;
+
+impl<T: Terminal> NamedCommandParser<T> for StatusParser {
+    type Context = ();
+    type Error = Infallible;
+
+    fn parse(&self, _: &str) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        Ok(Box::new(StatusCommand))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn aliases(&self) -> Vec<Cow<'static, str>> {
+        vec!["st".into(), "stat".into()]
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "status".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "Shows the status.".into(),
+            usage: Cow::default(),
+            examples: vec![],
+        }
+    }
+
+    fn category(&self) -> Option<Cow<'static, str>> {
+        Some("Diagnostics".into())
+    }
+}
+
+fn categorised_commander() -> Commander<(), Infallible, Mock<'static>> {
+    Commander::new(vec![Box::new(quit::Parser::default()), Box::new(StatusParser)])
+}
+
+#[test]
+fn markdown_grouped() {
+    assert_eq!("\
+# Commands
+
+## Diagnostics
+
+### `status` (`st`, `stat`)
+
+Shows the status.
+
+**Usage:** `status`
+
+## Other
+
+### `quit` (`q`)
+
+Exits the program, optionally with the given status code.
+
+**Usage:** `quit [<code>]`
+
+**Examples:**
+
+* exit with status code 2: `quit 2`
+", markdown(&categorised_commander(), "Commands"));
+}
+
+#[test]
+fn man_page_grouped() {
+    let man_page = man_page(&categorised_commander(), "commands");
+    assert!(man_page.contains(".SH DIAGNOSTICS\n.SS st, stat, status\nShows the status.\n"), "{man_page}");
+    assert!(man_page.contains(".SH OTHER\n.SS q, quit\n"), "{man_page}");
+    assert!(!man_page.contains(".SH COMMANDS"), "{man_page}");
+}
+
+#[test]
+fn json_grouped() {
+    let json = json(&categorised_commander()).to_json();
+    assert!(json.starts_with(r#"{"commands":[{"name":"status","shorthand":null,"aliases":["st","stat"],"category":"Diagnostics","#), "{json}");
+    assert!(json.contains(r#"{"name":"quit","shorthand":"q","aliases":[],"category":null,"#), "{json}");
+}
+
+struct RetiredParser;
+
+impl<T: Terminal> NamedCommandParser<T> for RetiredParser {
+    type Context = ();
+    type Error = Infallible;
+
+    fn parse(&self, _: &str) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        Ok(Box::new(StatusCommand))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "state".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "Shows the state.".into(),
+            usage: Cow::default(),
+            examples: vec![],
+        }
+    }
+
+    fn is_deprecated(&self) -> bool {
+        true
+    }
+
+    fn replacement(&self) -> Option<Cow<'static, str>> {
+        Some("status".into())
+    }
+}
+
+#[test]
+fn deprecated_content() {
+    let commander = Commander::<(), Infallible, Mock>::new(vec![Box::new(RetiredParser), Box::new(StatusParser)]);
+    let markdown = markdown(&commander, "Commands");
+    assert!(markdown.contains("### `state`\n\nShows the state.\n\n**Deprecated:** use `status` instead.\n\n**Usage:** `state`\n"), "{markdown}");
+    let man_page = man_page(&commander, "commands");
+    assert!(man_page.contains(".SS state\nShows the state.\n.PP\nDeprecated; use \\fBstatus\\fR instead.\n.PP\nUsage:\n"), "{man_page}");
+    let json = json(&commander).to_json();
+    assert!(json.contains(r#""name":"state","shorthand":null,"aliases":[],"category":null,"purpose":"Shows the state.","deprecated":true,"replacement":"status","#), "{json}");
+}
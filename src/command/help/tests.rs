@@ -5,12 +5,12 @@ use crate::command::{
     NamedCommandParser, ParseCommandError,
 };
 use crate::looper::Looper;
-use crate::terminal::{lines, Mock, Terminal};
+use crate::terminal::{assert_snapshot, lines, Mock, OutputFormat, Terminal, TerminalSize, Value};
 use std::borrow::Cow;
 use std::convert::Infallible;
 use stanza::renderer::console::{Console, Decor};
 use stanza::renderer::Renderer;
-use crate::command::help::commands;
+use crate::command::help::{commands, groups};
 
 #[derive(Debug)]
 struct SampleCommand;
@@ -79,6 +79,116 @@ fn invoke() {
     assert!(output.contains("sample foo bar"));
 }
 
+#[test]
+fn invoke_ansi() {
+    let commander = Commander::<_, Infallible, _>::new(vec![
+        Box::new(super::Parser::default()),
+        Box::new(quit::Parser::default()),
+    ]);
+    let mut context = ();
+
+    let mut term = Mock::default().on_read_line(lines(&["help", "quit"]));
+    Looper::new(&mut term, &commander, &mut context).run().unwrap();
+    assert!(!term.output().contains('\x1b'));
+
+    let mut term = Mock::default().with_ansi(true).on_read_line(lines(&["help", "quit"]));
+    Looper::new(&mut term, &commander, &mut context).run().unwrap();
+    assert!(term.output().contains('\x1b'));
+}
+
+#[test]
+fn invoke_snapshot() {
+    let mut term = Mock::default().on_read_line(lines(&["help", "quit"]));
+    let commander = Commander::<_, Infallible, _>::new(vec![
+        Box::new(super::Parser::default()),
+        Box::new(quit::Parser::default()),
+        Box::new(SampleParser),
+    ]);
+    let mut context = ();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.run().unwrap();
+
+    assert_snapshot(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/command/help/snapshots/invoke.txt"),
+        &term.output(),
+    );
+}
+
+#[test]
+fn invoke_wide() {
+    let mut term = Mock::default()
+        .with_size(TerminalSize::new(200, 50))
+        .on_read_line(lines(&["help", "quit"]));
+    let commander = Commander::<_, Infallible, _>::new(vec![
+        Box::new(super::Parser::default()),
+        Box::new(quit::Parser::default()),
+        Box::new(SampleParser),
+    ]);
+    Looper::new(&mut term, &commander, &mut ()).run().unwrap();
+
+    let output = term.output();
+    let lines = output.lines().map(|line| line.trim_start_matches("+>> ")).collect::<Vec<_>>();
+    assert!(lines.iter().any(|line| line.trim_end() == "h, help         Displays a list of commands, their usage syntax and examples."));
+    let widest = lines.iter().map(|line| line.chars().count()).max().unwrap();
+    assert_eq!(16 + 120, widest);
+}
+
+#[test]
+fn invoke_narrow() {
+    let mut term = Mock::default()
+        .with_size(TerminalSize::new(40, 24))
+        .on_read_line(lines(&["help", "quit"]));
+    let commander = Commander::<_, Infallible, _>::new(vec![
+        Box::new(super::Parser::default()),
+        Box::new(quit::Parser::default()),
+        Box::new(SampleParser),
+    ]);
+    Looper::new(&mut term, &commander, &mut ()).run().unwrap();
+
+    assert_eq!("\
+    +>> h, help\n\
+    \x20   Displays a list of commands, their\n\
+    \x20   usage syntax and examples.\n\
+    \x20   usage: help [<category>] [--format\n\
+    \x20   <text|markdown|man|json>]\n\
+    \x20   example - export the list of\n\
+    \x20   commands as Markdown:\n\
+    \x20       help --format markdown\n\
+    \n\
+    q, quit\n\
+    \x20   Exits the program, optionally with\n\
+    \x20   the given status code.\n\
+    \x20   usage: quit [<code>]\n\
+    \x20   example - exit with status code 2:\n\
+    \x20       quit 2\n\
+    \n\
+    z, sample\n\
+    \x20   A sample command.\n\
+    \x20   usage: sample <alpha> <beta>\n\
+    \x20   example - do something great:\n\
+    \x20       sample foo bar\n\
+    \n\
+    +>> Exiting.\n", term.output());
+}
+
+#[test]
+fn invoke_fits_width() {
+    for columns in [56, 60, 80, 100] {
+        let mut term = Mock::default()
+            .with_size(TerminalSize::new(columns, 24))
+            .on_read_line(lines(&["help", "quit"]));
+        let commander = Commander::<_, Infallible, _>::new(vec![
+            Box::new(super::Parser::default()),
+            Box::new(quit::Parser::default()),
+        ]);
+        Looper::new(&mut term, &commander, &mut ()).run().unwrap();
+        assert!(term.output().starts_with("+>> Command"), "columns: {columns}");
+        for line in term.output().lines().map(|line| line.trim_start_matches("+>> ")) {
+            assert!(line.chars().count() <= columns, "columns: {columns}, line: '{line}'");
+        }
+    }
+}
+
 #[test]
 fn commands_content() {
     let commander = Commander::<_, _, Mock>::new(vec![
@@ -93,14 +203,18 @@ fn commands_content() {
             .suppress_inner_horizontal_border(),
     );
 
-    let s = renderer.render(&commands(&commander)).to_string();
+    let s = renderer.render(&commands(&commander, &groups(&commander)[0], 65)).to_string();
     assert_eq!("\
     ╔═══════════════╤═════════════════════════════════════════════════════════════════╗\n\
     ║Command        │Description                                                      ║\n\
     ║h, help        │Displays a list of commands, their usage syntax and examples.    ║\n\
-    ║               │usage: help                                                      ║\n\
-    ║q, quit        │Exits the program.                                               ║\n\
-    ║               │usage: quit                                                      ║\n\
+    ║               │usage: help [<category>] [--format <text|markdown|man|json>]     ║\n\
+    ║               │example - export the list of commands as Markdown:               ║\n\
+    ║               │    help --format markdown                                       ║\n\
+    ║q, quit        │Exits the program, optionally with the given status code.        ║\n\
+    ║               │usage: quit [<code>]                                             ║\n\
+    ║               │example - exit with status code 2:                               ║\n\
+    ║               │    quit 2                                                       ║\n\
     ║z, sample      │A sample command.                                                ║\n\
     ║               │usage: sample <alpha> <beta>                                     ║\n\
     ║               │example - do something great:                                    ║\n\
@@ -111,7 +225,324 @@ fn commands_content() {
 #[test]
 fn parse_error() {
     assert_eq!(
-        ParseCommandError("invalid arguments to 'help': 'foo'".into()),
-        NamedCommandParser::<Mock>::parse(&super::Parser::<(), Infallible>::default(), "foo").err().unwrap()
+        ParseCommandError::new("invalid arguments to 'help': '--foo'").with_span(0..5),
+        NamedCommandParser::<Mock>::parse(&super::Parser::<(), Infallible>::default(), "--foo").err().unwrap()
     );
 }
+
+#[test]
+fn parse_format_error() {
+    let parser = super::Parser::<(), Infallible>::default();
+    for (input, expected) in [
+        ("--format", ParseCommandError::new("invalid arguments to 'help': '--format'").with_span(0..8)),
+        ("--formats md", ParseCommandError::new("invalid arguments to 'help': '--formats md'").with_span(0..12)),
+        ("--format html", ParseCommandError::new("unsupported help format 'html'; expected one of text, markdown, man or json").with_span(9..13)),
+        ("--format=", ParseCommandError::new("unsupported help format ''; expected one of text, markdown, man or json").with_span(9..9)),
+    ] {
+        assert_eq!(expected, NamedCommandParser::<Mock>::parse(&parser, input).err().unwrap(), "input: {input}");
+    }
+}
+
+#[test]
+fn invoke_with_format() {
+    let commander = Commander::<_, Infallible, _>::new(vec![
+        Box::new(super::Parser::default().with_title("Sample console")),
+        Box::new(SampleParser),
+    ]);
+    let mut context = ();
+
+    for (line, expected) in [
+        ("help --format md", super::markdown(&commander, "Sample console")),
+        ("help --format=man", super::man_page(&commander, "Sample console")),
+        ("h --format json", format!("{}\n", super::json(&commander).to_json())),
+    ] {
+        let mut term = Mock::default();
+        Looper::new(&mut term, &commander, &mut context).run_once(line).unwrap();
+        assert_eq!(expected, term.output(), "line: {line}");
+    }
+
+    let mut term = Mock::default();
+    Looper::new(&mut term, &commander, &mut context).run_once("help --format text").unwrap();
+    assert!(term.output().starts_with("Command "));
+}
+
+#[test]
+fn invoke_machine_readable() {
+    let commander = Commander::<_, Infallible, _>::new(vec![
+        Box::new(super::Parser::default()),
+        Box::new(SampleParser),
+    ]);
+    let mut term = Mock::default().with_output_format(OutputFormat::Json);
+    Looper::new(&mut term, &commander, &mut ()).run_once("help").unwrap();
+    assert_eq!(format!(
+        "{}\n{}\n",
+        super::json(&commander).to_json(),
+        r#"{"command":"help","outcome":"applied"}"#
+    ), term.output());
+}
+
+#[test]
+fn invoke_csv() {
+    let commander = Commander::<_, Infallible, _>::new(vec![
+        Box::new(super::Parser::default()),
+        Box::new(SampleParser),
+    ]);
+    let mut term = Mock::default().with_output_format(OutputFormat::Csv);
+    Looper::new(&mut term, &commander, &mut ()).run_once("help").unwrap();
+    let Value::Object(members) = super::json(&commander) else { unreachable!() };
+    let Value::Array(commands) = &members[0].1 else { unreachable!() };
+    let records = commands.iter().map(|command| format!("{}\n", command.to_csv())).collect::<String>();
+    assert_eq!(format!("{records}help,applied\n"), term.output());
+    assert!(term.output().starts_with("help,h,[],,"));
+}
+
+struct CategorisedParser {
+    name: &'static str,
+    category: &'static str,
+}
+
+impl<T: Terminal> NamedCommandParser<T> for CategorisedParser {
+    type Context = ();
+    type Error = Infallible;
+
+    fn parse(&self, _: &str) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        Ok(Box::new(SampleCommand))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: format!("The {} command.", self.name).into(),
+            usage: Cow::default(),
+            examples: vec![],
+        }
+    }
+
+    fn category(&self) -> Option<Cow<'static, str>> {
+        Some(self.category.into())
+    }
+}
+
+fn categorised_commander() -> Commander<(), Infallible, Mock<'static>> {
+    Commander::new(vec![
+        Box::new(super::Parser::default()),
+        Box::new(CategorisedParser { name: "mount", category: "Storage" }),
+        Box::new(CategorisedParser { name: "ping", category: "Network" }),
+        Box::new(CategorisedParser { name: "unmount", category: "Storage" }),
+    ])
+    .with_category_order(["Storage"])
+}
+
+#[test]
+fn invoke_grouped() {
+    let commander = categorised_commander();
+    let mut term = Mock::default();
+    Looper::new(&mut term, &commander, &mut ()).run_once("help").unwrap();
+    let output = term.output();
+    assert_eq!(vec![
+        "Storage:",
+        "mount           The mount command.",
+        "                usage: mount",
+        "",
+        "unmount         The unmount command.",
+        "                usage: unmount",
+        "",
+        "Network:",
+        "ping            The ping command.",
+        "                usage: ping",
+        "",
+        "Other:",
+        "h, help         Displays a list of commands, their usage syntax and examples.",
+        "                usage: help [<category>] [--format <text|markdown|man|json>]",
+        "                example - export the list of commands as Markdown:",
+        "                    help --format markdown",
+        "",
+    ], output.lines().map(str::trim_end).collect::<Vec<_>>());
+}
+
+#[test]
+fn invoke_grouped_narrow() {
+    let commander = categorised_commander();
+    let mut term = Mock::default().with_size(TerminalSize::new(40, 24));
+    Looper::new(&mut term, &commander, &mut ()).run_once("help network").unwrap();
+    assert_eq!("\
+    Network:\n\
+    \n\
+    ping\n\
+    \x20   The ping command.\n\
+    \x20   usage: ping\n\
+    \n", term.output());
+}
+
+#[test]
+fn invoke_category() {
+    let commander = categorised_commander();
+    let mut context = ();
+    for line in ["help storage", "help STORAGE", "h  Storage "] {
+        let mut term = Mock::default();
+        Looper::new(&mut term, &commander, &mut context).run_once(line).unwrap();
+        assert!(term.output().starts_with("Storage:\nmount "), "line: {line}");
+        assert!(!term.output().contains("ping"), "line: {line}");
+    }
+
+    let mut term = Mock::default();
+    Looper::new(&mut term, &commander, &mut context).run_once("help other --format md").unwrap();
+    assert_eq!("\
+# Commands
+
+## Other
+
+### `help` (`h`)
+
+Displays a list of commands, their usage syntax and examples.
+
+**Usage:** `help [<category>] [--format <text|markdown|man|json>]`
+
+**Examples:**
+
+* export the list of commands as Markdown: `help --format markdown`
+", term.output());
+}
+
+#[test]
+fn invoke_unknown_category() {
+    let mut context = ();
+    for (commander, hint) in [
+        (categorised_commander(), "expected one of: Storage, Network, Other"),
+        (Commander::new(vec![Box::new(super::Parser::default()), Box::new(SampleParser)]), "no categories are defined"),
+    ] {
+        let mut term = Mock::default();
+        Looper::new(&mut term, &commander, &mut context).run_once("help admin").unwrap();
+        assert_eq!(format!("Invalid input: no such category 'admin'.\n    hint: {hint}\n"), term.output());
+    }
+}
+
+struct LegacyParser {
+    name: &'static str,
+    hidden: bool,
+}
+
+impl<T: Terminal> NamedCommandParser<T> for LegacyParser {
+    type Context = ();
+    type Error = Infallible;
+
+    fn parse(&self, _: &str) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        Ok(Box::new(SampleCommand))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "A legacy command.".into(),
+            usage: Cow::default(),
+            examples: vec![],
+        }
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    fn is_deprecated(&self) -> bool {
+        true
+    }
+
+    fn replacement(&self) -> Option<Cow<'static, str>> {
+        Some("sample".into())
+    }
+}
+
+#[test]
+fn invoke_hidden_and_deprecated() {
+    let commander = Commander::<_, Infallible, _>::new(vec![
+        Box::new(super::Parser::default()),
+        Box::new(LegacyParser { name: "legacy", hidden: false }),
+        Box::new(LegacyParser { name: "ancient", hidden: true }),
+        Box::new(SampleParser),
+    ]);
+    for line in ["help", "help --format md", "help --format man", "help --format json"] {
+        let mut term = Mock::default();
+        Looper::new(&mut term, &commander, &mut ()).run_once(line).unwrap();
+        assert!(term.output().contains("legacy"), "line: {line}");
+        assert!(!term.output().contains("ancient"), "line: {line}");
+    }
+
+    let mut term = Mock::default();
+    Looper::new(&mut term, &commander, &mut ()).run_once("help").unwrap();
+    let output = term.output();
+    let lines = output.lines().map(str::trim_end).collect::<Vec<_>>();
+    let legacy = lines.iter().position(|line| line.starts_with("legacy")).unwrap();
+    assert_eq!(vec![
+        "legacy          A legacy command.",
+        "                deprecated - use 'sample' instead",
+        "                usage: legacy",
+    ], lines[legacy..legacy + 3]);
+}
+
+#[test]
+fn invoke_hidden_does_not_widen() {
+    let commander = Commander::<_, Infallible, _>::new(vec![
+        Box::new(super::Parser::default()),
+        Box::new(LegacyParser { name: "legacy", hidden: false }),
+        Box::new(LegacyParser { name: "an_ancient_command_with_a_very_long_name", hidden: true }),
+        Box::new(SampleParser),
+    ]);
+    let mut term = Mock::default();
+    Looper::new(&mut term, &commander, &mut ()).run_once("help").unwrap();
+    let output = term.output();
+    assert!(output.lines().any(|line| line.trim_end() == "legacy          A legacy command."), "{output}");
+}
+
+struct ListParser;
+
+impl<T: Terminal> NamedCommandParser<T> for ListParser {
+    type Context = ();
+    type Error = Infallible;
+
+    fn parse(&self, _: &str) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        Ok(Box::new(SampleCommand))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        Some("l".into())
+    }
+
+    fn aliases(&self) -> Vec<Cow<'static, str>> {
+        vec!["ls".into(), "dir".into()]
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "list".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: "Lists the files.".into(),
+            usage: Cow::default(),
+            examples: vec![],
+        }
+    }
+}
+
+#[test]
+fn invoke_aliases() {
+    let commander = Commander::<_, Infallible, _>::new(vec![Box::new(super::Parser::default()), Box::new(ListParser)]);
+    let mut term = Mock::default();
+    Looper::new(&mut term, &commander, &mut ()).run_once("help").unwrap();
+    let output = term.output();
+    assert!(output.lines().any(|line| line.trim_end() == "l, ls, dir, list Lists the files."), "{output}");
+}
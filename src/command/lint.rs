@@ -20,6 +20,11 @@ pub enum Lint {
     ExampleCommandHasExcessWhitespace,
     ExampleCommandIsEmpty,
     ExampleCommandBeginsWithCommandName,
+    DeprecationHasNoReplacement,
+    ReplacementWithoutDeprecation,
+    ReplacementHasExcessWhitespace,
+    ReplacementIsEmpty,
+    ReplacementIsCommandName,
 }
 
 impl Lint {
@@ -57,6 +62,7 @@ pub fn assert<C, E, T>(parser: &impl NamedCommandParser<T, Context = C , Error =
 pub fn validate<C, E, T>(parser: &impl NamedCommandParser<T, Context = C , Error = E>) -> Vec<Lint> {
     let mut failed = vec![];
     validate_description(&parser.name(), &parser.description(), &mut failed);
+    validate_deprecation(&parser.name(), parser.is_deprecated(), parser.replacement().as_deref(), &mut failed);
     failed
 }
 
@@ -67,7 +73,7 @@ fn validate_description(command_name: &str, desc: &Description, failed: &mut Vec
     no_excess_whitespace(purpose, PurposeHasExcessWhitespace, failed);
     if PurposeIsEmpty.assert(!purpose.is_empty(), failed) {
         PurposeDoesNotBeginWithUppercase.assert(purpose.chars().next().unwrap().is_uppercase(), failed);
-        PurposeDoesNotEndWithPeriod.assert(purpose.chars().rev().next().unwrap() == '.', failed);
+        PurposeDoesNotEndWithPeriod.assert(purpose.ends_with('.'), failed);
     }
 
     no_excess_whitespace(usage, UsageHasExcessWhitespace, failed);
@@ -87,7 +93,7 @@ fn validate_example(command_name: &str, ex: &Example, failed: &mut Vec<Lint>) {
     no_excess_whitespace(scenario, ExampleScenarioHasExcessWhitespace, failed);
     if ExampleScenarioIsEmpty.assert(!scenario.is_empty(), failed) {
         ExampleScenarioBeginsWithUppercase.assert(!scenario.chars().next().unwrap().is_uppercase(), failed);
-        ExampleScenarioEndsWithPeriod.assert(scenario.chars().rev().next().unwrap() != '.', failed);
+        ExampleScenarioEndsWithPeriod.assert(!scenario.ends_with('.'), failed);
     }
 
     no_excess_whitespace(command, ExampleCommandHasExcessWhitespace, failed);
@@ -96,6 +102,22 @@ fn validate_example(command_name: &str, ex: &Example, failed: &mut Vec<Lint>) {
     }
 }
 
+/// Ensures that a deprecated command refers to its replacement, and that the replacement refers to
+/// some other command.
+fn validate_deprecation(command_name: &str, deprecated: bool, replacement: Option<&str>, failed: &mut Vec<Lint>) {
+    let Some(replacement) = replacement else {
+        DeprecationHasNoReplacement.assert(!deprecated, failed);
+        return;
+    };
+
+    ReplacementWithoutDeprecation.assert(deprecated, failed);
+    no_excess_whitespace(replacement, ReplacementHasExcessWhitespace, failed);
+    if ReplacementIsEmpty.assert(!replacement.is_empty(), failed) {
+        let identifier = replacement.split(' ').next().unwrap();
+        ReplacementIsCommandName.assert(identifier != command_name, failed);
+    }
+}
+
 fn no_excess_whitespace(s: &str, lint: Lint, failed: &mut Vec<Lint>) {
     lint.assert(s.trim() == s, failed);
 }
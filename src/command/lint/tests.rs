@@ -7,7 +7,9 @@ use crate::terminal::Mock;
 
 struct Parser {
     name: &'static str,
-    description: Description
+    description: Description,
+    deprecated: bool,
+    replacement: Option<&'static str>
 }
 
 impl<T> NamedCommandParser<T> for Parser {
@@ -29,6 +31,14 @@ impl<T> NamedCommandParser<T> for Parser {
     fn description(&self) -> Description {
         self.description.clone()
     }
+
+    fn is_deprecated(&self) -> bool {
+        self.deprecated
+    }
+
+    fn replacement(&self) -> Option<Cow<'static, str>> {
+        self.replacement.map(Into::into)
+    }
 }
 
 fn assert_description_pedantic(command_name: &'static str, description: Description) {
@@ -38,10 +48,25 @@ fn assert_description_pedantic(command_name: &'static str, description: Descript
 fn assert_description(command_name: &'static str, description: Description, exclusions: &[Lint]) {
     lint::assert::<_, _, Mock>(&Parser {
         name: command_name,
-        description
+        description,
+        deprecated: false,
+        replacement: None
     }, exclusions);
 }
 
+fn validate_deprecation(deprecated: bool, replacement: Option<&'static str>) -> Vec<Lint> {
+    lint::validate::<_, _, Mock>(&Parser {
+        name: "frobnicate",
+        description: Description {
+            purpose: "Frobnicates the gogomobile's auxiliary fuel pump.".into(),
+            usage: Cow::default(),
+            examples: Vec::default()
+        },
+        deprecated,
+        replacement
+    })
+}
+
 #[test]
 fn validate_description_empty_usage_passes() {
     assert_description_pedantic("frobnicate", Description {
@@ -231,4 +256,20 @@ fn validate_description_example_command_begins_with_command_fails() {
             }
         ]
     });
-}
\ No newline at end of file
+}
+
+#[test]
+fn validate_deprecation_passes() {
+    assert_eq!(Vec::<Lint>::new(), validate_deprecation(false, None));
+    assert_eq!(Vec::<Lint>::new(), validate_deprecation(true, Some("pump flow")));
+    assert_eq!(Vec::<Lint>::new(), validate_deprecation(true, Some("frobnicate-pump")));
+}
+
+#[test]
+fn validate_deprecation_fails() {
+    assert_eq!(vec![Lint::DeprecationHasNoReplacement], validate_deprecation(true, None));
+    assert_eq!(vec![Lint::ReplacementWithoutDeprecation], validate_deprecation(false, Some("pump")));
+    assert_eq!(vec![Lint::ReplacementHasExcessWhitespace], validate_deprecation(true, Some(" pump")));
+    assert_eq!(vec![Lint::ReplacementIsEmpty], validate_deprecation(true, Some("")));
+    assert_eq!(vec![Lint::ReplacementIsCommandName], validate_deprecation(true, Some("frobnicate 2")));
+}
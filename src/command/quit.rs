@@ -2,21 +2,36 @@
 
 use std::borrow::Cow;
 use std::marker::PhantomData;
-use crate::command::{ApplyCommandError, ApplyOutcome, Command, Description, NamedCommandParser, ParseCommandError};
+use crate::command::{ApplyCommandError, ApplyOutcome, Command, Description, Example, NamedCommandParser, ParseCommandError};
 use crate::looper::Looper;
 use crate::terminal::Terminal;
 
 /// The `quit` command. Once applied, will stop the [`RunFlag`](crate::looper::RunFlag). When control returns
 /// to the [`Looper`], it will realise that the flag is in the [`RunFlag::Stopped`](crate::looper::RunFlag::Stopped)
 /// state, and will immediately return from the loop.
+///
+/// If an exit code is given, it is set as the [`ExitStatus`](crate::looper::ExitStatus) returned by
+/// [`Looper::run`]; otherwise, the status is left unchanged.
 pub struct Quit<C, E> {
+    code: Option<u8>,
     __phantom_data: PhantomData<(C, E)>
 }
 
 impl<C, E> Default for Quit<C, E> {
     fn default() -> Self {
         Self {
-            __phantom_data: PhantomData::default(),
+            code: None,
+            __phantom_data: PhantomData,
+        }
+    }
+}
+
+impl<C, E> Quit<C, E> {
+    /// Creates a [`Quit`] command that exits with the given status code.
+    pub fn with_code(code: u8) -> Self {
+        Self {
+            code: Some(code),
+            __phantom_data: PhantomData,
         }
     }
 }
@@ -27,7 +42,12 @@ impl<C, E, T: Terminal> Command<T> for Quit<C, E> {
 
     fn apply(&mut self, looper: &mut Looper<C, E, T>) -> Result<ApplyOutcome, ApplyCommandError<E>> {
         looper.run_flag().stop();
-        looper.terminal().print_line("Exiting.")?;
+        if let Some(code) = self.code {
+            looper.exit_status().set(code);
+        }
+        if !looper.terminal().output_format().is_machine_readable() {
+            looper.terminal().print_line("Exiting.")?;
+        }
         Ok(ApplyOutcome::Applied)
     }
 }
@@ -40,7 +60,7 @@ pub struct Parser<C, E> {
 impl<C, E> Default for Parser<C, E> {
     fn default() -> Self {
         Self {
-            __phantom_data: PhantomData::default(),
+            __phantom_data: PhantomData,
         }
     }
 }
@@ -50,7 +70,14 @@ impl<C: 'static, E: 'static, T: Terminal> NamedCommandParser<T> for Parser<C, E>
     type Error = E;
 
     fn parse(&self, s: &str) -> Result<Box<dyn Command<T, Context = C , Error = E>>, ParseCommandError> {
-        self.parse_no_args(s, Quit::default)
+        if s.is_empty() {
+            return Ok(Box::new(Quit::default()));
+        }
+        let code = s.parse().map_err(|_| {
+            ParseCommandError::new(format!("invalid arguments to '{}': '{s}'", <Self as NamedCommandParser<T>>::name(self)))
+                .with_span(0..s.len())
+        })?;
+        Ok(Box::new(Quit::with_code(code)))
     }
 
     fn shorthand(&self) -> Option<Cow<'static, str>> {
@@ -63,9 +90,12 @@ impl<C: 'static, E: 'static, T: Terminal> NamedCommandParser<T> for Parser<C, E>
     
     fn description(&self) -> Description {
         Description {
-            purpose: "Exits the program.".into(),
-            usage: Cow::default(),
-            examples: Vec::default()
+            purpose: "Exits the program, optionally with the given status code.".into(),
+            usage: "[<code>]".into(),
+            examples: vec![Example {
+                scenario: "exit with status code 2".into(),
+                command: "2".into(),
+            }]
         }
     }
 }
@@ -1,13 +1,40 @@
 // $coverage:ignore-start
 
 use std::convert::Infallible;
-use crate::command::{NamedCommandParser, ParseCommandError};
-use crate::terminal::Mock;
+use crate::command::{Commander, NamedCommandParser, ParseCommandError};
+use crate::looper::{ExitStatus, Looper};
+use crate::terminal::{lines, Mock};
 
 #[test]
 fn parse_error() {
     assert_eq!(
-        ParseCommandError("invalid arguments to 'quit': 'foo'".into()),
+        ParseCommandError::new("invalid arguments to 'quit': 'foo'").with_span(0..3),
         NamedCommandParser::<Mock>::parse(&super::Parser::<(), Infallible>::default(), "foo").err().unwrap()
     );
-}
\ No newline at end of file
+}
+
+#[test]
+fn parse_code_out_of_range() {
+    assert_eq!(
+        ParseCommandError::new("invalid arguments to 'quit': '256'").with_span(0..3),
+        NamedCommandParser::<Mock>::parse(&super::Parser::<(), Infallible>::default(), "256").err().unwrap()
+    );
+}
+
+#[test]
+fn exit_status() {
+    let mut term = Mock::default().on_read_line(lines(&["quit 3"]));
+    let commander = Commander::<_, Infallible, _>::new(vec![Box::new(super::Parser::default())]);
+    let mut context = ();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus(3), looper.run().unwrap());
+}
+
+#[test]
+fn exit_status_unchanged() {
+    let mut term = Mock::default().on_read_line(lines(&["quit"]));
+    let commander = Commander::<_, Infallible, _>::new(vec![Box::new(super::Parser::default())]);
+    let mut context = ();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::SUCCESS, looper.run().unwrap());
+}
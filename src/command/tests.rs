@@ -38,9 +38,7 @@ impl FromStr for SampleCommand {
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if !s.is_empty() {
-            return Err(ParseCommandError(
-                format!("invalid arguments to 'sample': '{s}'").into(),
-            ));
+            return Err(ParseCommandError::new(format!("invalid arguments to 'sample': '{s}'")).with_span(0..s.len()));
         }
         Ok(Self)
     }
@@ -84,7 +82,7 @@ fn invalid_command_parser_spec_implements_display() {
 
 #[test]
 fn parse_command_error_implements_display() {
-    assert_eq!("foo", ParseCommandError("foo".into()).to_string());
+    assert_eq!("foo", ParseCommandError::new("foo").to_string());
 }
 
 #[test]
@@ -103,44 +101,75 @@ fn commander() {
     assert_eq!(None, commander.parse("s").err());
     assert_eq!(None, commander.parse("sample").err());
     assert_eq!(
-        Some(ParseCommandError("empty command string".into())),
+        Some(ParseCommandError::new("empty command string")),
         commander.parse("").err()
     );
     assert_eq!(
-        Some(ParseCommandError("no command parser for ''".into())),
+        Some(ParseCommandError::new("no command parser for ''").with_span(0..0)),
         commander.parse(" ").err()
     );
     assert_eq!(
-        Some(ParseCommandError("no command parser for 'z'".into())),
+        Some(ParseCommandError::new("no command parser for 'z'").with_span(0..1)),
         commander.parse("z").err()
     );
     assert_eq!(
-        Some(ParseCommandError("no command parser for 'zzz'".into())),
+        Some(ParseCommandError::new("no command parser for 'zzz'").with_span(0..3)),
         commander.parse("zzz").err()
     );
     assert_eq!(
-        Some(ParseCommandError("no command parser for 'zzz'".into())),
+        Some(ParseCommandError::new("no command parser for 'zzz'").with_span(0..3)),
         commander.parse("zzz ").err()
     );
     assert_eq!(None, commander.parse("s ").err());
     assert_eq!(
-        Some(ParseCommandError(
-            "invalid arguments to 'sample': ' '".into()
-        )),
+        Some(ParseCommandError::new("invalid arguments to 'sample': ' '").with_span(2..3)),
         commander.parse("s  ").err()
     );
     assert_eq!(
-        Some(ParseCommandError(
-            "invalid arguments to 'sample': 'z'".into()
-        )),
+        Some(ParseCommandError::new("invalid arguments to 'sample': 'z'").with_span(2..3)),
         commander.parse("s z").err()
     );
 }
 
+#[test]
+fn commander_parser_for() {
+    let parsers: Vec<Box<dyn NamedCommandParser<TestTerminal, Context=_, Error=_>>> = vec![Box::new(Parser)];
+    let commander = Commander::new(parsers);
+    assert_eq!(Some("sample".into()), commander.parser_for("s").map(|parser| parser.name()));
+    assert_eq!(Some("sample".into()), commander.parser_for("sample z").map(|parser| parser.name()));
+    assert!(!commander.parser_for("s").unwrap().is_destructive());
+    assert!(commander.parser_for("z").is_none());
+    assert!(commander.parser_for("").is_none());
+}
+
+#[test]
+fn parse_command_error_incomplete() {
+    let err = ParseCommandError::incomplete("more");
+    assert!(err.incomplete);
+    assert!(!ParseCommandError::new("less").incomplete);
+    assert_eq!(ParseCommandError::incomplete("more"), err.clone().offset(3));
+    assert!(err.with_span(0..1).offset(3).incomplete);
+}
+
+#[test]
+fn commander_parse_with_payload() {
+    let parsers: Vec<Box<dyn NamedCommandParser<TestTerminal, Context=_, Error=_>>> = vec![Box::new(Parser)];
+    let commander = Commander::new(parsers);
+    assert_eq!(
+        Some(ParseCommandError::new("'sample' does not accept a payload")),
+        commander.parse_with_payload("s", "payload").err()
+    );
+    assert_eq!(
+        Some(ParseCommandError::new("no command parser for 'z'").with_span(0..1)),
+        commander.parse_with_payload("z", "payload").err()
+    );
+}
+
 struct TestCommandParser {
     short: Option<Cow<'static, str>>,
     long: Cow<'static, str>,
-    example_command: Cow<'static, str>
+    example_command: Cow<'static, str>,
+    category: Option<Cow<'static, str>>,
 }
 
 impl<T: Terminal> NamedCommandParser<T> for TestCommandParser {
@@ -163,6 +192,10 @@ impl<T: Terminal> NamedCommandParser<T> for TestCommandParser {
         self.long.clone()
     }
 
+    fn category(&self) -> Option<Cow<'static, str>> {
+        self.category.clone()
+    }
+
     fn description(&self) -> Description {
         Description {
             purpose: Cow::default(),
@@ -183,12 +216,14 @@ fn commander_duplicate_short() {
         Box::new(TestCommandParser {
             short: Some("g".into()),
             long: "g1".into(),
-            example_command: "42".into()
+            example_command: "42".into(),
+            category: None
         }),
         Box::new(TestCommandParser {
             short: Some("g".into()),
             long: "g2".into(),
-            example_command: "42".into()
+            example_command: "42".into(),
+            category: None
         }),
     ];
     assert_eq!(
@@ -203,12 +238,14 @@ fn commander_duplicate_long() {
         Box::new(TestCommandParser {
             short: Some("g".into()),
             long: "gg".into(),
-            example_command: "42".into()
+            example_command: "42".into(),
+            category: None
         }),
         Box::new(TestCommandParser {
             short: Some("h".into()),
             long: "gg".into(),
-            example_command: "42".into()
+            example_command: "42".into(),
+            category: None
         }),
     ];
     assert_eq!(
@@ -225,12 +262,14 @@ fn commander_duplicate_short_in_long() {
         Box::new(TestCommandParser {
             short: Some("gg".into()),
             long: "hh".into(),
-            example_command: "42".into()
+            example_command: "42".into(),
+            category: None
         }),
         Box::new(TestCommandParser {
             short: Some("hh".into()),
             long: "ii".into(),
-            example_command: "42".into()
+            example_command: "42".into(),
+            category: None
         }),
     ];
     assert_eq!(
@@ -247,12 +286,14 @@ fn commander_duplicate_long_in_short() {
         Box::new(TestCommandParser {
             short: Some("gg".into()),
             long: "hh".into(),
-            example_command: "42".into()
+            example_command: "42".into(),
+            category: None
         }),
         Box::new(TestCommandParser {
             short: Some("ii".into()),
             long: "gg".into(),
-            example_command: "42".into()
+            example_command: "42".into(),
+            category: None
         }),
     ];
     assert_eq!(
@@ -269,7 +310,8 @@ fn commander_long_name_too_short() {
         vec![Box::new(TestCommandParser {
             short: Some("g".into()),
             long: "h".into(),
-            example_command: "42".into()
+            example_command: "42".into(),
+            category: None
         })];
     assert_eq!(
         Some(InvalidCommandParserSpec(
@@ -285,7 +327,8 @@ fn commander_unparsable_example() {
         vec![Box::new(TestCommandParser {
             short: Some("g".into()),
             long: "ggg".into(),
-            example_command: "foo".into()
+            example_command: "foo".into(),
+            category: None
         })];
     assert_eq!(
         Some(InvalidCommandParserSpec(
@@ -295,12 +338,298 @@ fn commander_unparsable_example() {
     );
 }
 
+#[test]
+fn commander_blank_category() {
+    let parsers: Vec<Box<dyn NamedCommandParser<TestTerminal, Context=_, Error=_>>> =
+        vec![Box::new(TestCommandParser {
+            short: None,
+            long: "gg".into(),
+            example_command: "42".into(),
+            category: Some(" ".into())
+        })];
+    assert_eq!(
+        Some(InvalidCommandParserSpec(
+            "invalid category of command 'gg': must not be blank".into()
+        )),
+        Commander::try_from(parsers).err()
+    );
+}
+
+#[test]
+fn commander_categories() {
+    let parser = |long: &'static str, category: Option<&'static str>| {
+        Box::new(TestCommandParser {
+            short: None,
+            long: long.into(),
+            example_command: "42".into(),
+            category: category.map(Into::into)
+        }) as Box<dyn NamedCommandParser<TestTerminal, Context=_, Error=_>>
+    };
+    let commander = Commander::new(vec![
+        parser("aa", Some("Storage")),
+        parser("bb", None),
+        parser("cc", Some("Admin")),
+        parser("dd", Some("Network")),
+        parser("ee", Some("Admin")),
+    ]);
+    assert_eq!(vec!["Admin", "Network", "Storage"], commander.categories());
+
+    let commander = commander.with_category_order(["Storage", "Unused", "Admin"]);
+    assert_eq!(vec!["Storage", "Admin", "Network"], commander.categories());
+}
+
+struct LegacyParser {
+    name: &'static str,
+    hidden: bool,
+    deprecated: bool,
+    replacement: Option<&'static str>,
+}
+
+impl<T: Terminal> NamedCommandParser<T> for LegacyParser {
+    type Context = TestContext;
+    type Error = Infallible;
+
+    fn parse(
+        &self,
+        _: &str,
+    ) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        Ok(Box::new(SampleCommand))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: Cow::default(),
+            usage: Cow::default(),
+            examples: Vec::default(),
+        }
+    }
+
+    fn category(&self) -> Option<Cow<'static, str>> {
+        Some(self.name.to_uppercase().into())
+    }
+
+    fn is_hidden(&self) -> bool {
+        self.hidden
+    }
+
+    fn is_deprecated(&self) -> bool {
+        self.deprecated
+    }
+
+    fn replacement(&self) -> Option<Cow<'static, str>> {
+        self.replacement.map(Into::into)
+    }
+}
+
+fn legacy_parsers(replacement: &'static str) -> Vec<Box<dyn NamedCommandParser<TestTerminal, Context = TestContext, Error = Infallible>>> {
+    vec![
+        Box::new(LegacyParser { name: "old", hidden: true, deprecated: true, replacement: Some(replacement) }),
+        Box::new(LegacyParser { name: "older", hidden: false, deprecated: true, replacement: None }),
+        Box::new(LegacyParser { name: "new", hidden: false, deprecated: false, replacement: None }),
+        Box::new(Parser),
+    ]
+}
+
+#[test]
+fn commander_replacement() {
+    for replacement in ["new", "new --verbose", "s"] {
+        let commander = Commander::try_from(legacy_parsers(replacement)).unwrap();
+        assert!(commander.parse("old").is_ok(), "replacement: {replacement}");
+        assert_eq!(vec!["NEW", "OLDER"], commander.categories(), "replacement: {replacement}");
+    }
+}
+
+#[test]
+fn commander_unknown_replacement() {
+    assert_eq!(
+        Some(InvalidCommandParserSpec("unknown replacement 'newer' for command 'old'".into())),
+        Commander::try_from(legacy_parsers("newer")).err()
+    );
+}
+
+#[test]
+fn commander_deprecated_replacement() {
+    assert_eq!(
+        Some(InvalidCommandParserSpec("replacement 'older' for command 'old' is deprecated".into())),
+        Commander::try_from(legacy_parsers("older")).err()
+    );
+}
+
+struct AliasedParser {
+    name: &'static str,
+    aliases: Vec<&'static str>,
+}
+
+impl<T: Terminal> NamedCommandParser<T> for AliasedParser {
+    type Context = TestContext;
+    type Error = Infallible;
+
+    fn parse(
+        &self,
+        s: &str,
+    ) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        SampleCommand::from_str(s)
+            .map(|cmd| Box::new(cmd) as Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>)
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn aliases(&self) -> Vec<Cow<'static, str>> {
+        self.aliases.iter().map(|&alias| alias.into()).collect()
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: Cow::default(),
+            usage: Cow::default(),
+            examples: Vec::default(),
+        }
+    }
+}
+
+#[test]
+fn commander_aliases() {
+    let commander = Commander::<_, _, TestTerminal>::new(vec![
+        Box::new(AliasedParser { name: "list", aliases: vec!["ls", "l"] }),
+        Box::new(Parser),
+    ]);
+    for input in ["list", "ls", "l", "s"] {
+        assert!(commander.parse(input).is_ok(), "input: {input}");
+    }
+    assert_eq!("list", commander.parser_for("l x").unwrap().name());
+    assert_eq!(
+        Some(ParseCommandError::new("invalid arguments to 'sample': 'x'").with_span(3..4)),
+        commander.parse("ls x").err()
+    );
+}
+
+#[test]
+fn commander_alias_conflicts() {
+    for (aliases, expected) in [
+        (vec!["ls", "ls"], "duplicate command parser for 'ls'"),
+        (vec!["s"], "duplicate command parser for 's'"),
+        (vec!["sample"], "duplicate command parser for 'sample'"),
+        (vec![""], "invalid alias '' of command 'list': must be non-empty and free of whitespace"),
+        (vec!["l s"], "invalid alias 'l s' of command 'list': must be non-empty and free of whitespace"),
+    ] {
+        let parsers: Vec<Box<dyn NamedCommandParser<TestTerminal, Context = _, Error = _>>> = vec![
+            Box::new(Parser),
+            Box::new(AliasedParser { name: "list", aliases }),
+        ];
+        assert_eq!(Some(InvalidCommandParserSpec(expected.into())), Commander::try_from(parsers).err());
+    }
+
+    let parsers: Vec<Box<dyn NamedCommandParser<TestTerminal, Context = _, Error = _>>> = vec![
+        Box::new(AliasedParser { name: "list", aliases: vec!["sample"] }),
+        Box::new(Parser),
+    ];
+    assert_eq!(
+        Some(InvalidCommandParserSpec("duplicate command parser for 'sample'".into())),
+        Commander::try_from(parsers).err()
+    );
+}
+
+fn matching_commander() -> Commander<TestContext, Infallible, TestTerminal> {
+    Commander::new(vec![
+        Box::new(AliasedParser { name: "subtract", aliases: vec!["minus"] }),
+        Box::new(AliasedParser { name: "submit", aliases: vec![] }),
+        Box::new(AliasedParser { name: "status", aliases: vec!["STAT"] }),
+        Box::new(AliasedParser { name: "Stat", aliases: vec![] }),
+        Box::new(LegacyParser { name: "statistics", hidden: true, deprecated: false, replacement: None }),
+        Box::new(Parser),
+    ])
+}
+
+#[test]
+fn commander_exact_matching() {
+    let commander = matching_commander();
+    for (input, expected) in [
+        ("subtract", Some("subtract")),
+        ("MINUS", None),
+        ("sub", None),
+        ("STAT", Some("status")),
+        ("Stat", Some("Stat")),
+        ("stat", None),
+    ] {
+        assert_eq!(expected, commander.parser_for(input).map(|parser| parser.name()).as_deref(), "input: {input}");
+    }
+    assert_eq!(
+        Some(ParseCommandError::new("no command parser for 'sub'").with_span(0..3)),
+        commander.parse("sub 42").err()
+    );
+}
+
+#[test]
+fn commander_case_insensitive_matching() {
+    let commander = matching_commander().with_case_insensitive(true);
+    for (input, expected) in [
+        ("SUBTRACT", Some("subtract")),
+        ("Minus", Some("subtract")),
+        ("S", Some("sample")),
+        ("STAT", Some("status")),
+        ("Stat", Some("Stat")),
+        ("sub", None),
+    ] {
+        assert_eq!(expected, commander.parser_for(input).map(|parser| parser.name()).as_deref(), "input: {input}");
+    }
+    assert_eq!(
+        Some(ParseCommandError::new("ambiguous command 'stat'; could be any of 'STAT', 'Stat'").with_span(0..4)),
+        commander.parse("stat").err()
+    );
+}
+
+#[test]
+fn commander_prefix_matching() {
+    let commander = matching_commander().with_prefix_matching(true);
+    for (input, expected) in [
+        ("subt", Some("subtract")),
+        ("subm", Some("submit")),
+        ("sa", Some("sample")),
+        ("s", Some("sample")),
+        ("stati", None),
+        ("SUBT", None),
+        ("statistics", Some("statistics")),
+    ] {
+        assert_eq!(expected, commander.parser_for(input).map(|parser| parser.name()).as_deref(), "input: {input}");
+    }
+    assert!(commander.parse("subt").is_ok());
+    assert_eq!(
+        Some(ParseCommandError::new("ambiguous command 'sub'; could be any of 'submit', 'subtract'").with_span(0..3)),
+        commander.parse("sub 42").err()
+    );
+    assert_eq!(
+        Some(ParseCommandError::new("no command parser for 'stati'").with_span(0..5)),
+        commander.parse("stati").err()
+    );
+
+    let commander = commander.with_case_insensitive(true);
+    assert_eq!("subtract", commander.parser_for("SUBT").unwrap().name());
+    assert_eq!(
+        Some(ParseCommandError::new("ambiguous command 'St'; could be any of 'Stat', 'status'").with_span(0..2)),
+        commander.parse("St").err()
+    );
+}
+
 fn application_error() -> ApplyCommandError<&'static str> {
     ApplyCommandError::Application("data")
 }
 
 fn access_terminal_error() -> ApplyCommandError<Infallible> {
-    ApplyCommandError::AccessTerminal(AccessTerminalError("data".into()))
+    ApplyCommandError::AccessTerminal(AccessTerminalError::other("data"))
 }
 
 #[test]
@@ -314,6 +643,6 @@ fn apply_command_error_variants() {
     assert_eq!(Some("data"), application_error().application());
     assert_eq!(None, application_error().access_terminal());
 
-    assert_eq!(Some(AccessTerminalError("data".into())), access_terminal_error().access_terminal());
+    assert_eq!(Some(AccessTerminalError::other("data")), access_terminal_error().access_terminal());
     assert_eq!(None, access_terminal_error().application());
 }
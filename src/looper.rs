@@ -1,24 +1,25 @@
 //! The mechanism for iteratively running commands based on successive user input. This module fulfils the
 //! 'loop' part of a REPL application.
 
+mod heredoc;
+
+pub use heredoc::*;
+
+use std::error::Error;
 use std::fmt::Display;
-use crate::command::{ApplyCommandError, ApplyOutcome, Commander, read_command};
-use crate::terminal::{AccessTerminalError, Terminal};
+use std::process::ExitCode;
+use crate::command::{ApplyCommandError, ApplyOutcome, Command, Commander, ParseCommandError};
+use crate::terminal::{AccessTerminalError, AccessTerminalErrorKind, Report, Terminal, Value};
 
 /// Whether or not the looper is running. By setting the flag to [`RunFlag::Stopped`], a command
 /// can signal the termination of the application.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum RunFlag {
     Running,
+    #[default]
     Stopped
 }
 
-impl Default for RunFlag {
-    fn default() -> Self {
-        Self::Stopped
-    }
-}
-
 impl RunFlag {
     /// Signals a start.
     pub fn start(&mut self) {
@@ -36,6 +37,162 @@ impl RunFlag {
     }
 }
 
+/// The exit status of a [`Looper`] run, which an application typically propagates to the process.
+/// Any command can set the exit status; in addition, the [`Looper`] sets it to
+/// [`ExitStatus::FAILURE`] when a command yields an error, unless a nonzero status was already set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExitStatus(pub u8);
+
+impl ExitStatus {
+    /// Successful termination.
+    pub const SUCCESS: Self = Self(0);
+
+    /// Generic failure.
+    pub const FAILURE: Self = Self(1);
+
+    /// Sets the status code.
+    pub fn set(&mut self, code: u8) {
+        self.0 = code;
+    }
+
+    /// Is this a successful (zero) exit status?
+    pub fn is_success(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Maps the status onto a [`std::process::ExitCode`], for returning from `main`.
+    pub fn exit_code(self) -> ExitCode {
+        self.into()
+    }
+}
+
+impl From<ExitStatus> for ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        Self::from(status.0)
+    }
+}
+
+/// How the [`Looper`] reacts to an [`AccessTerminalError`], either while reading a command or
+/// when percolated from an applied command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalErrorAction {
+    /// The error is fatal; [`Looper::run`] returns it to the caller.
+    Fail,
+
+    /// The loop is stopped as though the [`RunFlag`] was stopped; [`Looper::run`] returns normally.
+    Stop,
+
+    /// The error is ignored and the user is prompted for the next command.
+    Resume,
+}
+
+/// Signature of a function that decides how the [`Looper`] reacts to an [`AccessTerminalError`].
+pub type TerminalErrorHandler = fn(&AccessTerminalError) -> TerminalErrorAction;
+
+/// The default [`TerminalErrorHandler`]. Stops the loop at the end of input (e.g., Ctrl-D), resumes
+/// after an interrupt (e.g., Ctrl-C), and fails on any other error.
+pub fn default_terminal_error_handler(err: &AccessTerminalError) -> TerminalErrorAction {
+    match err.kind() {
+        AccessTerminalErrorKind::EndOfInput => TerminalErrorAction::Stop,
+        AccessTerminalErrorKind::Interrupted => TerminalErrorAction::Resume,
+        _ => TerminalErrorAction::Fail,
+    }
+}
+
+/// Whether a line of input is complete, as decided by a [`ContinuationCheck`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Continuation {
+    /// The input is complete and can be parsed.
+    Complete,
+
+    /// The input ends with a backslash, which escapes the line break. The backslash is removed
+    /// and the next line is appended directly, as in a shell.
+    Escaped,
+
+    /// The input has an unterminated quote or unclosed brackets. The next line is appended
+    /// after a line break.
+    Unbalanced,
+}
+
+/// Signature of a function that decides whether the [`Looper`] should read a continuation line
+/// before parsing the input. It is given the input read so far.
+pub type ContinuationCheck = fn(&str) -> Continuation;
+
+/// The default [`ContinuationCheck`]. Continues the input if it ends with an unescaped backslash,
+/// or if it has an unterminated single or double quote, or more opening brackets (`(`, `[` or `{`)
+/// than closing ones outside of quotes. A backslash escapes the next character, except within
+/// single quotes.
+pub fn default_continuation_check(s: &str) -> Continuation {
+    let trailing_backslashes = s.chars().rev().take_while(|&ch| ch == '\\').count();
+    if trailing_backslashes % 2 == 1 {
+        return Continuation::Escaped;
+    }
+
+    let mut quote = None;
+    let mut depth = 0_usize;
+    let mut escaped = false;
+    for ch in s.chars() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match (quote, ch) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('"') | None, '\\') => escaped = true,
+            (None, '\'' | '"') => quote = Some(ch),
+            (None, '(' | '[' | '{') => depth += 1,
+            (None, ')' | ']' | '}') => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    if quote.is_some() || depth > 0 {
+        Continuation::Unbalanced
+    } else {
+        Continuation::Complete
+    }
+}
+
+/// Converts errors into [`Report`]s for printing by the [`Looper`]: both errors yielded by
+/// commands and errors parsing the user's input. An application may supply its own presenter to
+/// [`Looper::with_error_presenter`]; e.g., to add hints.
+pub trait ErrorPresenter<E> {
+    /// Presents an error yielded by a command.
+    fn command_error(&self, err: &E) -> Report;
+
+    /// Presents an error parsing the given input line. The default implementation produces an
+    /// `Invalid input` report with the error message and, if the error has a span, a snippet of
+    /// the input underlining the offending part.
+    fn invalid_input(&self, input: &str, err: &ParseCommandError) -> Report {
+        let report = Report::new("Invalid input", err);
+        match &err.span {
+            Some(span) => report.with_snippet(input, span.clone()),
+            None => report,
+        }
+    }
+}
+
+/// The default [`ErrorPresenter`], which produces a `Command error` report with just the error message.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultErrorPresenter;
+
+impl<E: Display> ErrorPresenter<E> for DefaultErrorPresenter {
+    fn command_error(&self, err: &E) -> Report {
+        Report::new("Command error", err)
+    }
+}
+
+/// An [`ErrorPresenter`] for application errors that implement [`Error`], which includes the
+/// error's source chain in the report.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SourceChainErrorPresenter;
+
+impl<E: Error + 'static> ErrorPresenter<E> for SourceChainErrorPresenter {
+    fn command_error(&self, err: &E) -> Report {
+        Report::new("Command error", err).with_sources_of(err)
+    }
+}
+
 /// Controls the main application loop. Encapsulates a [`Terminal`] device for interfacing with the user,
 /// a [`Commander`] for parsing commands, a [`RunFlag`] that tracks the state of the application, and
 /// a caller-specified context that represents the rest of the application state.
@@ -43,7 +200,14 @@ pub struct Looper<'a, C, E, T: Terminal> {
     terminal: &'a mut T,
     commander: &'a Commander<C, E, T>,
     run_flag: RunFlag,
-    context: &'a mut C
+    exit_status: ExitStatus,
+    context: &'a mut C,
+    terminal_error_handler: TerminalErrorHandler,
+    prompt: &'a dyn Prompt<C>,
+    error_presenter: Option<&'a dyn ErrorPresenter<E>>,
+    assume_yes: bool,
+    continuation_check: Option<ContinuationCheck>,
+    heredoc: bool,
 }
 
 impl<'a, C, E, T: Terminal> Looper<'a, C, E, T> {
@@ -53,10 +217,71 @@ impl<'a, C, E, T: Terminal> Looper<'a, C, E, T> {
             terminal,
             commander,
             run_flag: RunFlag::default(),
-            context
+            exit_status: ExitStatus::default(),
+            context,
+            terminal_error_handler: default_terminal_error_handler,
+            prompt: &DefaultPrompt,
+            error_presenter: None,
+            assume_yes: false,
+            continuation_check: None,
+            heredoc: false,
         }
     }
 
+    /// Specifies the [`ErrorPresenter`] for reporting command errors and invalid input. By default,
+    /// [`DefaultErrorPresenter`] is used.
+    #[must_use]
+    pub fn with_error_presenter(mut self, error_presenter: &'a dyn ErrorPresenter<E>) -> Self {
+        self.error_presenter = Some(error_presenter);
+        self
+    }
+
+    /// Specifies whether destructive commands (see [`NamedCommandParser::is_destructive`](crate::command::NamedCommandParser::is_destructive)) are
+    /// applied without asking the user for confirmation. By default, the user is asked; where the
+    /// user cannot be asked (the input is not [interactive](Terminal::is_interactive), or the
+    /// output format is machine-readable), destructive commands are skipped with an error.
+    #[must_use]
+    pub fn with_assume_yes(mut self, assume_yes: bool) -> Self {
+        self.assume_yes = assume_yes;
+        self
+    }
+
+    /// Specifies the [`ContinuationCheck`] for reading input that spans several lines; e.g.,
+    /// [`default_continuation_check`]. By default, there is no check, and every line is parsed as it
+    /// is read, unless a parser signals that the input is [`ParseCommandError::incomplete`].
+    #[must_use]
+    pub fn with_continuation_check(mut self, check: ContinuationCheck) -> Self {
+        self.continuation_check = Some(check);
+        self
+    }
+
+    /// Specifies whether heredoc blocks are recognised. When enabled, a command line ending with a
+    /// [`HeredocMarker`] (e.g., `publish topic <<EOF`) is followed by the lines of a payload, up to a
+    /// line comprising just the terminator (e.g., `EOF`). The payload is passed to
+    /// [`Commander::parse_with_payload`]. Input that ends before the terminator is a parse error.
+    /// By default, heredoc blocks are not recognised.
+    #[must_use]
+    pub fn with_heredoc(mut self, heredoc: bool) -> Self {
+        self.heredoc = heredoc;
+        self
+    }
+
+    /// Specifies the [`Prompt`] displayed before reading each command. By default,
+    /// [`DefaultPrompt`] is used.
+    #[must_use]
+    pub fn with_prompt(mut self, prompt: &'a dyn Prompt<C>) -> Self {
+        self.prompt = prompt;
+        self
+    }
+
+    /// Specifies how [`Looper::run`] reacts to an [`AccessTerminalError`]. By default,
+    /// [`default_terminal_error_handler`] is used.
+    #[must_use]
+    pub fn with_terminal_error_handler(mut self, handler: TerminalErrorHandler) -> Self {
+        self.terminal_error_handler = handler;
+        self
+    }
+
     /// A mutable reference to the underlying [`Terminal`] interface.
     pub fn terminal(&mut self) -> &mut T {
         self.terminal
@@ -73,6 +298,12 @@ impl<'a, C, E, T: Terminal> Looper<'a, C, E, T> {
         &mut self.run_flag
     }
 
+    /// A mutable reference to the [`ExitStatus`]. This is exposed so that any command can set the
+    /// status returned by [`Looper::run`].
+    pub fn exit_status(&mut self) -> &mut ExitStatus {
+        &mut self.exit_status
+    }
+
     /// Split-borrow of the underlying components. Used when you need to reference two or more
     /// of these simultaneously, which wouldn't otherwise pass the borrow checker.
     pub fn split(&mut self) -> (&mut T, &Commander<C, E, T>, &mut C) {
@@ -86,19 +317,64 @@ impl<'a, C, E, T: Terminal> Looper<'a, C, E, T> {
 }
 
 /// The outcome of the last executed command. Used to present a slightly different prompt.
-enum LastCommandOutcome {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LastCommandOutcome {
+    /// The command was applied (or no command has been run yet).
     Applied,
+
+    /// The command was skipped, or the input was interrupted.
     Skipped,
+
+    /// The command yielded an error.
     Erred
 }
 
-impl LastCommandOutcome {
-    fn prompt(&self) -> &'static str {
-        match self {
+/// Renders the prompt that is displayed before reading each command. The application supplies
+/// its own implementation to [`Looper::with_prompt`] to show things like the connected environment
+/// or current namespace, obtained from the application context `C`.
+///
+/// Any `Fn(LastCommandOutcome, &C) -> String` closure is also a [`Prompt`].
+pub trait Prompt<C> {
+    /// Renders the prompt, given the outcome of the last command and the application context.
+    fn render(&self, outcome: LastCommandOutcome, context: &C) -> String;
+
+    /// Renders the prompt displayed before reading a continuation line of multi-line input. The
+    /// default implementation returns `... `.
+    fn render_continuation(&self, _context: &C) -> String {
+        "... ".into()
+    }
+}
+
+impl<C, F: Fn(LastCommandOutcome, &C) -> String> Prompt<C> for F {
+    fn render(&self, outcome: LastCommandOutcome, context: &C) -> String {
+        self(outcome, context)
+    }
+}
+
+/// The default [`Prompt`]: `+>> `, `->> ` or `!>> `, depending on whether the last command was
+/// applied, skipped or erred, respectively.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultPrompt;
+
+impl<C> Prompt<C> for DefaultPrompt {
+    fn render(&self, outcome: LastCommandOutcome, _: &C) -> String {
+        match outcome {
             LastCommandOutcome::Applied => "+>> ",
             LastCommandOutcome::Skipped => "->> ",
             LastCommandOutcome::Erred => "!>> "
+        }.into()
+    }
+}
+
+impl From<LastCommandOutcome> for Value {
+    /// Converts the outcome to a string value: `applied`, `skipped` or `erred`.
+    fn from(outcome: LastCommandOutcome) -> Self {
+        match outcome {
+            LastCommandOutcome::Applied => "applied",
+            LastCommandOutcome::Skipped => "skipped",
+            LastCommandOutcome::Erred => "erred",
         }
+        .into()
     }
 }
 
@@ -114,37 +390,321 @@ impl From<ApplyOutcome> for LastCommandOutcome {
 impl<'a, C, E: Display, T: Terminal> Looper<'a, C, E, T> {
     /// Starts the loop, blocking until one of the commands internally terminates the loop.
     ///
-    /// If any of the commands yields some other error, it will be printed to the user and the next
-    /// command will be executed (as per the user's terminal input). Only an [`AccessTerminalError`] will
-    /// percolate up the call stack.
+    /// If the input cannot be parsed, or any of the commands yields some other error, it will be
+    /// printed to the user and the next command will be executed (as per the user's terminal
+    /// input). An [`AccessTerminalError`] is handed to the [`TerminalErrorHandler`], which decides
+    /// whether the error is fatal (percolating up the call stack), stops the loop, or is ignored.
+    ///
+    /// With the [`default_terminal_error_handler`], reaching the end of input stops the loop, and
+    /// this method returns `Ok`. (Formerly, the end of input was returned as an error. A handler
+    /// that returns [`TerminalErrorAction::Fail`] for every error restores that behaviour.)
+    ///
+    /// On completion, returns the [`ExitStatus`] set by the commands. If a command yielded an error,
+    /// or the input could not be parsed and the terminal is not
+    /// [interactive](Terminal::is_interactive) (e.g., a script is piped in), and no other status was
+    /// set, the status will be [`ExitStatus::FAILURE`]. (An interactive user simply corrects the
+    /// input.)
+    ///
+    /// In a machine-readable [`OutputFormat`](crate::terminal::OutputFormat), prompts are not
+    /// printed, errors are emitted as [`Value`]s (see [`Report::to_value`]), and each command is
+    /// followed by an object with the members `command` (the input line) and `outcome` (`applied`,
+    /// `skipped` or `erred`), so that the output comprises only JSON lines or CSV records (plus
+    /// whatever the commands print).
     ///
     /// This method may be called repeatedly. Calling it after the looper has returned will
-    /// start a new loop, resetting the [`RunFlag`] before running the first command. It is up
-    /// to the caller to reset the application context.
+    /// start a new loop, resetting the [`RunFlag`] and the [`ExitStatus`] before running the first
+    /// command. It is up to the caller to reset the application context.
     ///
     /// # Errors
-    /// [`AccessTerminalError`] if the terminal device could not be accessed for reading or writing.
-    pub fn run(&mut self) -> Result<(), AccessTerminalError> {
+    /// [`AccessTerminalError`] if the terminal device could not be accessed for reading or writing,
+    /// and the [`TerminalErrorHandler`] deemed the error fatal.
+    pub fn run(&mut self) -> Result<ExitStatus, AccessTerminalError> {
         self.run_flag.start();
+        self.exit_status = ExitStatus::default();
         let mut last_command_outcome = LastCommandOutcome::Applied;
         while self.run_flag.is_running() {
-            let mut command = read_command(self, last_command_outcome.prompt())?;
-            let result = command.apply(self);
-            match result {
-                Ok(apply_outcome) => {
-                    last_command_outcome = apply_outcome.into();
-                },
-                Err(ApplyCommandError::Application(err)) => {
-                    self.terminal.print_line(&format!("Command error: {err}."))?;
-                    last_command_outcome = LastCommandOutcome::Erred;
-                },
-                Err(ApplyCommandError::AccessTerminal(err)) => {
-                    return Err(err)
+            let prompt = self.prompt.render(last_command_outcome, self.context);
+            match self.read_command(&prompt) {
+                Ok((line, Ok(command))) => {
+                    let result = self.apply(&line, command);
+                    last_command_outcome = self.handle(result)?;
+                    self.emit_outcome(&line, last_command_outcome)?;
+                }
+                Ok((line, Err(err))) => {
+                    self.invalid_input(&line, &err)?;
+                    if !self.terminal.is_interactive() && self.exit_status.is_success() {
+                        self.exit_status = ExitStatus::FAILURE;
+                    }
+                }
+                Err(err) => last_command_outcome = self.handle(Err(ApplyCommandError::AccessTerminal(err)))?,
+            }
+        }
+
+        Ok(self.exit_status)
+    }
+
+    /// Runs exactly one command, parsed from the given line, without prompting the user. This is
+    /// used for one-shot invocations; e.g., from a shell script.
+    ///
+    /// If the line cannot be parsed, the parse error is printed and the returned status will be
+    /// [`ExitStatus::FAILURE`]. Command errors are handled as per [`Looper::run`].
+    ///
+    /// If heredoc blocks are enabled (see [`Looper::with_heredoc`]), the line may be followed by the
+    /// lines of a payload and the terminator, separated by line breaks.
+    ///
+    /// # Errors
+    /// [`AccessTerminalError`] if the terminal device could not be accessed for reading or writing,
+    /// and the [`TerminalErrorHandler`] deemed the error fatal.
+    pub fn run_once(&mut self, line: &str) -> Result<ExitStatus, AccessTerminalError> {
+        self.run_flag.start();
+        self.exit_status = ExitStatus::default();
+        let line = line.trim();
+        let (first, rest) = line.split_once('\n').unwrap_or((line, ""));
+        let (line, result) = match self.parse_heredoc(first, |_, marker| Ok(marker.collect(rest.lines())))? {
+            Some(parsed) => parsed,
+            None => (line, self.commander.parse(line)),
+        };
+        match result {
+            Ok(command) => {
+                let result = self.apply(line, command);
+                let outcome = self.handle(result)?;
+                self.emit_outcome(line, outcome)?;
+            }
+            Err(err) => {
+                self.invalid_input(line, &err)?;
+                self.exit_status = ExitStatus::FAILURE;
+            }
+        }
+        self.run_flag.stop();
+        Ok(self.exit_status)
+    }
+
+    /// Runs either a single command or the interactive loop, depending on the given process
+    /// arguments (excluding the program name), as interpreted by [`command_line`]. Typically invoked
+    /// with `std::env::args().skip(1)`.
+    ///
+    /// A leading `--yes` (or `-y`) argument applies destructive commands without asking for
+    /// confirmation, as per [`Looper::with_assume_yes`], for the duration of this call; e.g.,
+    /// `app --yes -c "drop all"`.
+    ///
+    /// # Errors
+    /// [`AccessTerminalError`] if the terminal device could not be accessed for reading or writing,
+    /// and the [`TerminalErrorHandler`] deemed the error fatal.
+    pub fn run_args<S: AsRef<str>>(&mut self, args: impl IntoIterator<Item = S>) -> Result<ExitStatus, AccessTerminalError> {
+        let mut args = args.into_iter().peekable();
+        let yes = args.next_if(|arg| matches!(arg.as_ref(), "--yes" | "-y")).is_some();
+        let assume_yes = self.assume_yes;
+        self.assume_yes |= yes;
+        let result = match command_line(args) {
+            Ok(None) => self.run(),
+            Ok(Some(line)) => self.run_once(&line),
+            Err(err) => self
+                .error_presenter()
+                .invalid_input("", &err)
+                .print(self.terminal)
+                .map(|()| ExitStatus::FAILURE),
+        };
+        self.assume_yes = assume_yes;
+        result
+    }
+
+    /// Prints a prompt, unless the terminal is in a machine-readable output format.
+    fn print_prompt(&mut self, prompt: &str) -> Result<(), AccessTerminalError> {
+        if self.terminal.output_format().is_machine_readable() {
+            return Ok(());
+        }
+        self.terminal.print(prompt)
+    }
+
+    /// Emits the outcome of the command parsed from the given line, if the terminal is in a
+    /// machine-readable output format.
+    fn emit_outcome(&mut self, line: &str, outcome: LastCommandOutcome) -> Result<(), AccessTerminalError> {
+        if !self.terminal.output_format().is_machine_readable() {
+            return Ok(());
+        }
+        self.terminal.emit(&Value::object([("command", line.into()), ("outcome", outcome.into())]))
+    }
+
+    /// Reports input that could not be parsed, followed by its (erred) outcome.
+    fn invalid_input(&mut self, line: &str, err: &ParseCommandError) -> Result<(), AccessTerminalError> {
+        self.error_presenter().invalid_input(line, err).print(self.terminal)?;
+        self.emit_outcome(line, LastCommandOutcome::Erred)
+    }
+
+    /// The [`ErrorPresenter`] in effect.
+    fn error_presenter(&self) -> &'a dyn ErrorPresenter<E> {
+        self.error_presenter.unwrap_or(&DefaultErrorPresenter)
+    }
+
+    /// Reads a command from the terminal, following a prompt. Returns the trimmed input along with
+    /// the parsed command or the parse error.
+    ///
+    /// The input may span several lines, if the [`ContinuationCheck`] deems a line incomplete, or if
+    /// the parser returns an incomplete error. Each continuation line is preceded by the continuation
+    /// prompt. Reaching the end of input before the input is complete is a parse error.
+    #[allow(clippy::type_complexity)]
+    fn read_command(&mut self, prompt: &str) -> Result<(String, Result<Box<dyn Command<T, Context = C, Error = E>>, ParseCommandError>), AccessTerminalError> {
+        self.print_prompt(prompt)?;
+        let mut input = String::default();
+        loop {
+            let read = match self.terminal.read_line() {
+                Ok(read) => read,
+                Err(err) if err.kind() == AccessTerminalErrorKind::EndOfInput && !input.is_empty() => {
+                    return Ok((input.trim().into(), Err(ParseCommandError::new("unterminated input"))));
+                }
+                Err(err) => return Err(err),
+            };
+            input.push_str(read.trim_end_matches(['\n', '\r']));
+
+            let continuation = self.continuation_check.map_or(Continuation::Complete, |check| check(&input));
+            if continuation == Continuation::Complete {
+                let line = input.trim();
+                if let Some((line, result)) = self.parse_heredoc(line, Self::read_heredoc)? {
+                    return Ok((line.into(), result));
+                }
+                match self.commander.parse(line) {
+                    Err(err) if err.incomplete => {}
+                    result => return Ok((line.into(), result)),
+                }
+            }
+
+            if continuation == Continuation::Escaped {
+                input.pop();
+            } else {
+                input.push('\n');
+            }
+            let prompt = self.prompt.render_continuation(self.context);
+            self.print_prompt(&prompt)?;
+        }
+    }
+
+    /// Parses a command from a line ending with a heredoc marker, if heredoc blocks are enabled,
+    /// obtaining the payload with the given function. Returns the command line (without the marker)
+    /// along with the parsed command or the parse error, or [`None`] if the line has no marker.
+    #[allow(clippy::type_complexity)]
+    fn parse_heredoc<'l>(
+        &mut self,
+        line: &'l str,
+        payload: impl FnOnce(&mut Self, &HeredocMarker<'l>) -> Result<Result<String, ParseCommandError>, AccessTerminalError>,
+    ) -> Result<Option<(&'l str, Result<Box<dyn Command<T, Context = C, Error = E>>, ParseCommandError>)>, AccessTerminalError> {
+        let Some(marker) = self.heredoc.then(|| HeredocMarker::find(line)).flatten() else {
+            return Ok(None);
+        };
+        let result = payload(self, &marker)?
+            .and_then(|payload| self.commander.parse_with_payload(marker.command, &payload));
+        Ok(Some((marker.command, result)))
+    }
+
+    /// Reads the lines of a heredoc payload from the terminal up to the terminator, preceding each
+    /// with the continuation prompt. The lines are joined with `\n`. Reaching the end of input
+    /// before the terminator is a parse error.
+    fn read_heredoc(&mut self, marker: &HeredocMarker) -> Result<Result<String, ParseCommandError>, AccessTerminalError> {
+        let mut payload = vec![];
+        loop {
+            let prompt = self.prompt.render_continuation(self.context);
+            self.print_prompt(&prompt)?;
+            let read = match self.terminal.read_line() {
+                Ok(read) => read,
+                Err(err) if err.kind() == AccessTerminalErrorKind::EndOfInput => return Ok(Err(marker.unterminated())),
+                Err(err) => return Err(err),
+            };
+            let line = read.trim_end_matches(['\n', '\r']);
+            if marker.is_terminator(line) {
+                return Ok(Ok(payload.join("\n")));
+            }
+            payload.push(line.to_owned());
+        }
+    }
+
+    /// Applies a command parsed from the given line. If the command is deprecated, a warning is
+    /// printed first. If the command is destructive, the user is first asked to confirm, unless
+    /// confirmations are assumed; declining skips the command. Where the user cannot be asked
+    /// (the input is not interactive, or the output format is machine-readable), the command is
+    /// skipped with an error, setting the [`ExitStatus::FAILURE`] status unless some other status
+    /// was set.
+    fn apply(&mut self, line: &str, mut command: Box<dyn Command<T, Context = C, Error = E>>) -> Result<ApplyOutcome, ApplyCommandError<E>> {
+        if let Some(parser) = self.commander.parser_for(line).filter(|parser| parser.is_deprecated()) {
+            let warning = match parser.replacement() {
+                Some(replacement) => format!("'{}' is deprecated; use '{replacement}' instead", parser.name()),
+                None => format!("'{}' is deprecated", parser.name()),
+            };
+            if self.terminal.output_format().is_machine_readable() {
+                self.terminal.emit(&Value::object([("warning", warning.into())]))?;
+            } else {
+                self.terminal.print_warning(&format!("Warning: {warning}."))?;
+            }
+        }
+        if !self.assume_yes {
+            if let Some(parser) = self.commander.parser_for(line).filter(|parser| parser.is_destructive()) {
+                if !self.terminal.is_interactive() || self.terminal.output_format().is_machine_readable() {
+                    let message = format!("'{}' is destructive and cannot be confirmed here", parser.name());
+                    Report::new("Confirmation required", message)
+                        .with_hint("use --yes to apply destructive commands without confirmation")
+                        .print(self.terminal)?;
+                    if self.exit_status.is_success() {
+                        self.exit_status = ExitStatus::FAILURE;
+                    }
+                    return Ok(ApplyOutcome::Skipped);
+                }
+                let question = format!("Are you sure you want to run '{}'?", parser.name());
+                if !self.terminal.confirm(&question, Some(false))? {
+                    return Ok(ApplyOutcome::Skipped);
                 }
             }
         }
+        command.apply(self)
+    }
+
+    /// Handles the result of applying a command, returning the outcome for the next prompt.
+    fn handle(&mut self, result: Result<ApplyOutcome, ApplyCommandError<E>>) -> Result<LastCommandOutcome, AccessTerminalError> {
+        match result {
+            Ok(apply_outcome) => Ok(apply_outcome.into()),
+            Err(ApplyCommandError::Application(err)) => {
+                self.error_presenter().command_error(&err).print(self.terminal)?;
+                if self.exit_status.is_success() {
+                    self.exit_status = ExitStatus::FAILURE;
+                }
+                Ok(LastCommandOutcome::Erred)
+            },
+            Err(ApplyCommandError::AccessTerminal(err)) => {
+                match (self.terminal_error_handler)(&err) {
+                    TerminalErrorAction::Fail => Err(err),
+                    TerminalErrorAction::Stop => {
+                        self.run_flag.stop();
+                        Ok(LastCommandOutcome::Skipped)
+                    }
+                    TerminalErrorAction::Resume => Ok(LastCommandOutcome::Skipped),
+                }
+            }
+        }
+    }
+}
 
-        Ok(())
+/// Converts process arguments (excluding the program name) into a command line for a one-shot
+/// invocation.
+///
+/// * No arguments yield [`None`], implying an interactive session.
+/// * `-c <line>` yields `<line>` verbatim; e.g., `app -c "add 1.5"`.
+/// * Otherwise, the arguments are joined verbatim with single spaces; e.g., `app add 1.5`. The
+///   command parsers see the joined line, as though it had been typed; argument boundaries are not
+///   retained, so an argument containing whitespace is split by parsers that split on whitespace.
+///
+/// # Errors
+/// [`ParseCommandError`] if `-c` is not followed by exactly one argument.
+pub fn command_line<S: AsRef<str>>(args: impl IntoIterator<Item = S>) -> Result<Option<String>, ParseCommandError> {
+    let args = args.into_iter().collect::<Vec<_>>();
+    match args.first().map(AsRef::as_ref) {
+        None => Ok(None),
+        Some("-c") => match &args[1..] {
+            [line] => Ok(Some(line.as_ref().into())),
+            _ => Err(ParseCommandError::new("'-c' must be followed by exactly one command line argument")),
+        },
+        Some(_) => Ok(Some(
+            args.iter()
+                .map(AsRef::as_ref)
+                .collect::<Vec<_>>()
+                .join(" "),
+        )),
     }
 }
 
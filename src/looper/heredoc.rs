@@ -0,0 +1,73 @@
+//! Recognition of heredoc-style input blocks; e.g., `publish topic <<EOF`, followed by the lines of
+//! the payload, and terminated by a line comprising just `EOF`.
+
+use crate::command::ParseCommandError;
+
+/// A heredoc marker at the end of a command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeredocMarker<'a> {
+    /// The command line preceding the marker, without trailing whitespace.
+    pub command: &'a str,
+
+    /// The word that terminates the payload.
+    pub terminator: &'a str,
+}
+
+impl<'a> HeredocMarker<'a> {
+    /// Looks for a marker at the end of the given line, in the form `<<WORD`, where `WORD` comprises
+    /// alphanumeric characters and underscores, and may optionally be enclosed in single or double
+    /// quotes (e.g., `<<'EOF'`). Whitespace is permitted between `<<` and `WORD`. Returns [`None`] if
+    /// there is no marker, or if no command precedes it.
+    pub fn find(line: &'a str) -> Option<Self> {
+        let line = line.trim_end();
+        let index = line.rfind("<<")?;
+        let word = line[index + 2..].trim_start();
+        let terminator = ['\'', '"']
+            .into_iter()
+            .find_map(|quote| word.strip_prefix(quote)?.strip_suffix(quote))
+            .unwrap_or(word);
+        let command = line[..index].trim_end();
+        let valid = !terminator.is_empty()
+            && terminator.chars().all(|ch| ch.is_alphanumeric() || ch == '_')
+            && !command.is_empty();
+        valid.then_some(Self { command, terminator })
+    }
+
+    /// Is the given line the terminator? Trailing whitespace is ignored.
+    pub fn is_terminator(&self, line: &str) -> bool {
+        line.trim_end() == self.terminator
+    }
+
+    /// Collects the payload from the given lines, which follow the marker, up to (but excluding)
+    /// the terminator. The payload lines are joined with `\n`.
+    ///
+    /// # Errors
+    /// [`ParseCommandError`] if the terminator is missing, or if there is anything but blank lines
+    /// after it.
+    pub fn collect<'l>(&self, lines: impl IntoIterator<Item = &'l str>) -> Result<String, ParseCommandError> {
+        let mut lines = lines.into_iter();
+        let mut payload = vec![];
+        loop {
+            match lines.next() {
+                Some(line) if self.is_terminator(line) => break,
+                Some(line) => payload.push(line),
+                None => return Err(self.unterminated()),
+            }
+        }
+        if lines.any(|line| !line.trim().is_empty()) {
+            return Err(ParseCommandError::new(format!(
+                "unexpected input after heredoc terminator '{}'",
+                self.terminator
+            )));
+        }
+        Ok(payload.join("\n"))
+    }
+
+    /// The error reported when the input ends before the terminator.
+    pub(crate) fn unterminated(&self) -> ParseCommandError {
+        ParseCommandError::new(format!("unterminated heredoc; expected '{}'", self.terminator))
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,37 @@
+// $coverage:ignore-start
+
+use crate::command::ParseCommandError;
+use crate::looper::HeredocMarker;
+
+#[test]
+fn find() {
+    let marker = |command, terminator| Some(HeredocMarker { command, terminator });
+    assert_eq!(marker("publish topic", "EOF"), HeredocMarker::find("publish topic <<EOF"));
+    assert_eq!(marker("publish topic", "END_1"), HeredocMarker::find("publish topic << END_1  "));
+    assert_eq!(marker("publish", "EOF"), HeredocMarker::find("publish<<'EOF'"));
+    assert_eq!(marker("publish", "EOF"), HeredocMarker::find("publish <<\"EOF\""));
+    assert_eq!(marker("shift 1 << 2", "EOF"), HeredocMarker::find("shift 1 << 2 <<EOF"));
+    assert_eq!(None, HeredocMarker::find("publish topic"));
+    assert_eq!(None, HeredocMarker::find("<<EOF"));
+    assert_eq!(None, HeredocMarker::find("publish <<"));
+    assert_eq!(None, HeredocMarker::find("publish <<'EOF"));
+    assert_eq!(None, HeredocMarker::find("shift 1 << 2 3"));
+    assert_eq!(None, HeredocMarker::find("publish <<EOF-1"));
+}
+
+#[test]
+fn collect() {
+    let marker = HeredocMarker::find("publish <<EOF").unwrap();
+    assert!(marker.is_terminator("EOF  "));
+    assert!(!marker.is_terminator(" EOF"));
+    assert_eq!(Ok("{\n  \"a\": 1\n}".into()), marker.collect(["{", "  \"a\": 1", "}", "EOF", ""]));
+    assert_eq!(Ok(String::new()), marker.collect(["EOF"]));
+    assert_eq!(
+        Err(ParseCommandError::new("unterminated heredoc; expected 'EOF'")),
+        marker.collect(["{", "}"])
+    );
+    assert_eq!(
+        Err(ParseCommandError::new("unexpected input after heredoc terminator 'EOF'")),
+        marker.collect(["{}", "EOF", "more"])
+    );
+}
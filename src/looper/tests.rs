@@ -4,10 +4,12 @@ use crate::command::{
     quit, ApplyCommandError, ApplyOutcome, Command, Commander, Description, NamedCommandParser,
     ParseCommandError,
 };
-use crate::looper::{Looper, RunFlag};
+use crate::looper::{command_line, default_continuation_check, Continuation, DefaultPrompt, ErrorPresenter, SourceChainErrorPresenter, ExitStatus, LastCommandOutcome, Looper, Prompt, RunFlag, TerminalErrorAction};
 use crate::terminal::Invocation::ReadLine;
-use crate::terminal::{lines, AccessTerminalError, Invocation, Mock, Terminal};
+use crate::terminal::{feed, lines, AccessTerminalError, AccessTerminalErrorKind, Feed, Invocation, Mock, OutputFormat, Report, Terminal, Value};
 use std::borrow::Cow;
+use std::io;
+use std::process::ExitCode;
 use std::str::FromStr;
 use thiserror::Error;
 use Invocation::Print;
@@ -21,6 +23,10 @@ struct TestContext {
 #[error("{0}")]
 struct TestError(String);
 
+#[derive(Debug, Clone, Error)]
+#[error("{0}")]
+struct ChainedError(String, #[source] TestError);
+
 #[derive(Debug)]
 struct Echo {
     num: usize,
@@ -72,6 +78,56 @@ impl<T: Terminal> NamedCommandParser<T> for EchoParser {
     }
 }
 
+#[derive(Debug)]
+struct Measure {
+    num: usize,
+}
+
+impl<T: Terminal> Command<T> for Measure {
+    type Context = TestContext;
+    type Error = TestError;
+
+    fn apply(
+        &mut self,
+        looper: &mut Looper<Self::Context, Self::Error, T>,
+    ) -> Result<ApplyOutcome, ApplyCommandError<TestError>> {
+        let digits = u32::try_from(self.num.to_string().len()).unwrap();
+        looper.terminal.emit(&Value::object([("number", self.num.to_string().into()), ("digits", digits.into())]))?;
+        Ok(ApplyOutcome::Applied)
+    }
+}
+
+struct MeasureParser;
+
+impl<T: Terminal> NamedCommandParser<T> for MeasureParser {
+    type Context = TestContext;
+    type Error = TestError;
+
+    fn parse(
+        &self,
+        s: &str,
+    ) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        let num = usize::from_str(s).map_err(ParseCommandError::convert)?;
+        Ok(Box::new(Measure { num }))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "measure".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: Cow::default(),
+            usage: Cow::default(),
+            examples: Vec::default()
+        }
+    }
+}
+
 #[derive(Debug)]
 struct Respond {
     val: Result<ApplyOutcome, ApplyCommandError<TestError>>,
@@ -123,6 +179,252 @@ impl<T: Terminal> NamedCommandParser<T> for RespondParser {
     }
 }
 
+#[derive(Debug)]
+struct Exit {
+    code: u8,
+}
+
+impl<T: Terminal> Command<T> for Exit {
+    type Context = TestContext;
+    type Error = TestError;
+
+    fn apply(
+        &mut self,
+        looper: &mut Looper<Self::Context, Self::Error, T>,
+    ) -> Result<ApplyOutcome, ApplyCommandError<Self::Error>> {
+        looper.exit_status().set(self.code);
+        Ok(ApplyOutcome::Applied)
+    }
+}
+
+struct ExitParser;
+
+impl<T: Terminal> NamedCommandParser<T> for ExitParser {
+    type Context = TestContext;
+    type Error = TestError;
+
+    fn parse(
+        &self,
+        s: &str,
+    ) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        let code = u8::from_str(s).map_err(ParseCommandError::convert)?;
+        Ok(Box::new(Exit { code }))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "exit".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: Cow::default(),
+            usage: Cow::default(),
+            examples: Vec::default()
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Wipe;
+
+impl<T: Terminal> Command<T> for Wipe {
+    type Context = TestContext;
+    type Error = TestError;
+
+    fn apply(
+        &mut self,
+        looper: &mut Looper<Self::Context, Self::Error, T>,
+    ) -> Result<ApplyOutcome, ApplyCommandError<Self::Error>> {
+        looper.context().state = 0;
+        Ok(ApplyOutcome::Applied)
+    }
+}
+
+struct WipeParser;
+
+impl<T: Terminal> NamedCommandParser<T> for WipeParser {
+    type Context = TestContext;
+    type Error = TestError;
+
+    fn parse(
+        &self,
+        s: &str,
+    ) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        self.parse_no_args(s, || Wipe)
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        Some("w".into())
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "wipe".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: Cow::default(),
+            usage: Cow::default(),
+            examples: Vec::default()
+        }
+    }
+
+    fn is_destructive(&self) -> bool {
+        true
+    }
+}
+
+struct ClearParser;
+
+impl<T: Terminal> NamedCommandParser<T> for ClearParser {
+    type Context = TestContext;
+    type Error = TestError;
+
+    fn parse(
+        &self,
+        s: &str,
+    ) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        self.parse_no_args(s, || Wipe)
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "clear".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: Cow::default(),
+            usage: Cow::default(),
+            examples: Vec::default()
+        }
+    }
+
+    fn is_hidden(&self) -> bool {
+        true
+    }
+
+    fn is_deprecated(&self) -> bool {
+        true
+    }
+
+    fn replacement(&self) -> Option<Cow<'static, str>> {
+        Some("wipe".into())
+    }
+}
+
+#[derive(Debug)]
+struct Publish {
+    topic: String,
+    payload: String,
+}
+
+impl<T: Terminal> Command<T> for Publish {
+    type Context = TestContext;
+    type Error = TestError;
+
+    fn apply(
+        &mut self,
+        looper: &mut Looper<Self::Context, Self::Error, T>,
+    ) -> Result<ApplyOutcome, ApplyCommandError<Self::Error>> {
+        looper
+            .terminal()
+            .print_line(&format!("published to {}: {:?}", self.topic, self.payload))?;
+        Ok(ApplyOutcome::Applied)
+    }
+}
+
+struct PublishParser;
+
+impl<T: Terminal> NamedCommandParser<T> for PublishParser {
+    type Context = TestContext;
+    type Error = TestError;
+
+    fn parse(
+        &self,
+        s: &str,
+    ) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        let (topic, payload) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseCommandError::new("expected a topic and a payload"))?;
+        self.parse_with_payload(topic, payload)
+    }
+
+    fn parse_with_payload(
+        &self,
+        s: &str,
+        payload: &str,
+    ) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        Ok(Box::new(Publish {
+            topic: s.into(),
+            payload: payload.into(),
+        }))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "publish".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: Cow::default(),
+            usage: Cow::default(),
+            examples: Vec::default()
+        }
+    }
+}
+
+/// Sums whitespace-separated numbers, terminated by a semicolon. The input is incomplete until
+/// the semicolon is given.
+struct SumParser;
+
+impl<T: Terminal> NamedCommandParser<T> for SumParser {
+    type Context = TestContext;
+    type Error = TestError;
+
+    fn parse(
+        &self,
+        s: &str,
+    ) -> Result<Box<dyn Command<T, Context = Self::Context, Error = Self::Error>>, ParseCommandError> {
+        let Some(s) = s.strip_suffix(';') else {
+            return Err(ParseCommandError::incomplete("missing ';'"));
+        };
+        let num = s
+            .split_whitespace()
+            .map(usize::from_str)
+            .sum::<Result<_, _>>()
+            .map_err(ParseCommandError::convert)?;
+        Ok(Box::new(Echo { num }))
+    }
+
+    fn shorthand(&self) -> Option<Cow<'static, str>> {
+        None
+    }
+
+    fn name(&self) -> Cow<'static, str> {
+        "sum".into()
+    }
+
+    fn description(&self) -> Description {
+        Description {
+            purpose: Cow::default(),
+            usage: Cow::default(),
+            examples: Vec::default()
+        }
+    }
+}
+
 #[test]
 fn get_context() {
     let mut term = Mock::default();
@@ -184,6 +486,34 @@ fn echo_with_parse_error() {
     );
 }
 
+#[test]
+fn parse_error_with_span() {
+    let mut term = Mock::default().on_read_line(lines(&["quit foo", "  zap 1", "quit"]));
+    let commander = Commander::new(vec![Box::new(EchoParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.run().unwrap();
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("quit foo".into())),
+            Print("Invalid input: invalid arguments to 'quit': 'foo'.\n".into(), Ok(())),
+            Print("    quit foo\n".into(), Ok(())),
+            Print("         ^^^\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("  zap 1".into())),
+            Print("Invalid input: no command parser for 'zap'.\n".into(), Ok(())),
+            Print("    zap 1\n".into(), Ok(())),
+            Print("    ^^^\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("quit".into())),
+            Print("Exiting.\n".into(), Ok(())),
+        ],
+        term.invocations()
+    );
+}
+
 #[test]
 fn respond_skip() {
     let mut term = Mock::default().on_read_line(lines(&["respond", "quit"]));
@@ -222,7 +552,7 @@ fn respond_application_error() {
     ]);
     let mut context = TestContext::default();
     let mut looper = Looper::new(&mut term, &commander, &mut context);
-    looper.run().unwrap();
+    assert_eq!(ExitStatus::FAILURE, looper.run().unwrap());
 
     assert_eq!(
         &[
@@ -241,14 +571,12 @@ fn respond_application_error() {
 fn respond_terminal_error() {
     let mut term = Mock::default().on_read_line(lines(&["respond", "quit"]));
     let commander = Commander::new(vec![Box::new(RespondParser {
-        val: Err(ApplyCommandError::AccessTerminal(AccessTerminalError(
-            "terminal meltdown".into(),
-        ))),
+        val: Err(ApplyCommandError::AccessTerminal(AccessTerminalError::other("terminal meltdown"))),
     })]);
     let mut context = TestContext::default();
     let mut looper = Looper::new(&mut term, &commander, &mut context);
     assert_eq!(
-        AccessTerminalError("terminal meltdown".into()),
+        AccessTerminalError::other("terminal meltdown"),
         looper.run().unwrap_err()
     );
 
@@ -259,7 +587,929 @@ fn respond_terminal_error() {
 }
 
 #[test]
-fn run_flag_implements_debug() {
-    let flag = RunFlag::Running;
-    assert_eq!("Running", format!("{flag:?}"));
+fn end_of_input() {
+    let events = [Feed::line("echo 1"), Feed::EndOfInput];
+    let mut term = Mock::default().on_read_line(feed(&events));
+    let commander = Commander::new(vec![Box::new(EchoParser)]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.run().unwrap();
+    assert!(!looper.run_flag().is_running());
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("echo 1".into())),
+            Print("the number is 1\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Err("end of input".into())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn interrupt() {
+    let events = [Feed::Interrupt, Feed::line("echo 1"), Feed::EndOfInput];
+    let mut term = Mock::default().on_read_line(feed(&events));
+    let commander = Commander::new(vec![Box::new(EchoParser)]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.run().unwrap();
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Err("interrupted".into())),
+            Print("->> ".into(), Ok(())),
+            ReadLine(Ok("echo 1".into())),
+            Print("the number is 1\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Err("end of input".into())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn interrupt_in_command() {
+    let events = [Feed::line("respond"), Feed::EndOfInput];
+    let mut term = Mock::default().on_read_line(feed(&events));
+    let commander = Commander::new(vec![Box::new(RespondParser {
+        val: Err(ApplyCommandError::AccessTerminal(AccessTerminalError::interrupted())),
+    })]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.run().unwrap();
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("respond".into())),
+            Print("->> ".into(), Ok(())),
+            ReadLine(Err("end of input".into())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn custom_terminal_error_handler() {
+    let events = [Feed::Interrupt];
+    let mut term = Mock::default().on_read_line(feed(&events));
+    let commander = Commander::new(vec![Box::new(EchoParser)]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context)
+        .with_terminal_error_handler(|_| TerminalErrorAction::Fail);
+    let err = looper.run().unwrap_err();
+    assert_eq!(AccessTerminalErrorKind::Interrupted, err.kind());
+}
+
+#[test]
+fn default_terminal_error_handler() {
+    assert_eq!(
+        TerminalErrorAction::Stop,
+        super::default_terminal_error_handler(&AccessTerminalError::end_of_input())
+    );
+    assert_eq!(
+        TerminalErrorAction::Resume,
+        super::default_terminal_error_handler(&AccessTerminalError::interrupted())
+    );
+    assert_eq!(
+        TerminalErrorAction::Fail,
+        super::default_terminal_error_handler(&AccessTerminalError::from(io::Error::from(io::ErrorKind::BrokenPipe)))
+    );
+    assert_eq!(
+        TerminalErrorAction::Fail,
+        super::default_terminal_error_handler(&AccessTerminalError::other("meltdown"))
+    );
+}
+
+#[test]
+fn exit_status_preserved_after_error() {
+    let mut term = Mock::default().on_read_line(lines(&["exit 7", "respond", "quit"]));
+    let commander = Commander::new(vec![
+        Box::new(ExitParser),
+        Box::new(RespondParser {
+            val: Err(ApplyCommandError::Application(TestError("failed".into()))),
+        }),
+        Box::new(quit::Parser::default()),
+    ]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus(7), looper.run().unwrap());
+
+    // a subsequent run resets the exit status
+    let mut term = Mock::default().on_read_line(lines(&["quit"]));
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::SUCCESS, looper.run().unwrap());
+}
+
+#[test]
+fn exit_status_to_exit_code() {
+    assert!(ExitStatus::SUCCESS.is_success());
+    assert!(!ExitStatus::FAILURE.is_success());
+    assert_eq!(ExitCode::SUCCESS, ExitStatus::SUCCESS.exit_code());
+    assert_eq!(ExitCode::FAILURE, ExitStatus::FAILURE.exit_code());
+    assert_eq!(ExitCode::from(42), ExitCode::from(ExitStatus(42)));
+}
+
+#[test]
+fn run_once() {
+    let mut term = Mock::default();
+    let commander = Commander::new(vec![Box::new(EchoParser)]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::SUCCESS, looper.run_once("echo 5").unwrap());
+    assert!(!looper.run_flag().is_running());
+
+    assert_eq!(
+        &[Print("the number is 5\n".into(), Ok(()))],
+        term.invocations()
+    );
+}
+
+#[test]
+fn run_once_parse_error() {
+    let mut term = Mock::default();
+    let commander = Commander::new(vec![Box::new(EchoParser)]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::FAILURE, looper.run_once("echo x").unwrap());
+
+    assert_eq!(
+        &[Print("Invalid input: invalid digit found in string.\n".into(), Ok(()))],
+        term.invocations()
+    );
+}
+
+#[test]
+fn run_once_application_error() {
+    let mut term = Mock::default();
+    let commander = Commander::new(vec![Box::new(RespondParser {
+        val: Err(ApplyCommandError::Application(TestError("cooling pump exploded".into()))),
+    })]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::FAILURE, looper.run_once("respond").unwrap());
+
+    assert_eq!(
+        &[Print("Command error: cooling pump exploded.\n".into(), Ok(()))],
+        term.invocations()
+    );
+}
+
+#[test]
+fn run_once_terminal_error() {
+    let mut term = Mock::default();
+    let commander = Commander::new(vec![Box::new(RespondParser {
+        val: Err(ApplyCommandError::AccessTerminal(AccessTerminalError::other("terminal meltdown"))),
+    })]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(AccessTerminalError::other("terminal meltdown"), looper.run_once("respond").unwrap_err());
+}
+
+#[test]
+fn run_args() {
+    let commander = Commander::new(vec![Box::new(ExitParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+
+    let mut term = Mock::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus(4), looper.run_args(["-c", "exit 4"]).unwrap());
+
+    let mut term = Mock::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus(5), looper.run_args(["exit", "5"]).unwrap());
+
+    let mut term = Mock::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::FAILURE, looper.run_args(["-c"]).unwrap());
+    assert_eq!(
+        &[Print("Invalid input: '-c' must be followed by exactly one command line argument.\n".into(), Ok(()))],
+        term.invocations()
+    );
+
+    let mut term = Mock::default().on_read_line(lines(&["quit 6"]));
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus(6), looper.run_args(Vec::<String>::new()).unwrap());
+}
+
+#[test]
+fn destructive_confirmed() {
+    let mut term = Mock::default().on_read_line(lines(&["w", "y", "quit"]));
+    let commander = Commander::new(vec![Box::new(WipeParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext { state: 42 };
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.run().unwrap();
+    assert_eq!(0, context.state);
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("w".into())),
+            Print("Are you sure you want to run 'wipe'? [y/N] ".into(), Ok(())),
+            ReadLine(Ok("y".into())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("quit".into())),
+            Print("Exiting.\n".into(), Ok(())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn destructive_declined() {
+    let mut term = Mock::default().on_read_line(lines(&["wipe", "", "quit"]));
+    let commander = Commander::new(vec![Box::new(WipeParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext { state: 42 };
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::SUCCESS, looper.run().unwrap());
+    assert_eq!(42, context.state);
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("wipe".into())),
+            Print("Are you sure you want to run 'wipe'? [y/N] ".into(), Ok(())),
+            ReadLine(Ok("".into())),
+            Print("->> ".into(), Ok(())),
+            ReadLine(Ok("quit".into())),
+            Print("Exiting.\n".into(), Ok(())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn destructive_assume_yes() {
+    let mut term = Mock::default().on_read_line(lines(&["wipe", "quit"]));
+    let commander = Commander::new(vec![Box::new(WipeParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext { state: 42 };
+    let mut looper = Looper::new(&mut term, &commander, &mut context).with_assume_yes(true);
+    looper.run().unwrap();
+    assert_eq!(0, context.state);
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("wipe".into())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("quit".into())),
+            Print("Exiting.\n".into(), Ok(())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn destructive_not_interactive() {
+    let mut term = Mock::default()
+        .with_interactive(false)
+        .on_read_line(lines(&["wipe", "echo 1", "echo 2"]));
+    let commander = Commander::new(vec![Box::new(WipeParser), Box::new(EchoParser)]);
+    let mut context = TestContext { state: 42 };
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::FAILURE, looper.run().unwrap());
+    assert_eq!(42, context.state);
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("wipe".into())),
+            Print("Confirmation required: 'wipe' is destructive and cannot be confirmed here.\n".into(), Ok(())),
+            Print("    hint: use --yes to apply destructive commands without confirmation\n".into(), Ok(())),
+            Print("->> ".into(), Ok(())),
+            ReadLine(Ok("echo 1".into())),
+            Print("the number is 1\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("echo 2".into())),
+            Print("the number is 2\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Err("end of input".into())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn destructive_machine_readable() {
+    let mut term = Mock::default()
+        .with_output_format(OutputFormat::Json)
+        .on_read_line(lines(&["wipe", "echo 1"]));
+    let commander = Commander::new(vec![Box::new(WipeParser), Box::new(EchoParser)]);
+    let mut context = TestContext { state: 42 };
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::FAILURE, looper.run().unwrap());
+    assert_eq!(42, context.state);
+
+    let output = term.output();
+    let lines = output.lines().collect::<Vec<_>>();
+    assert!(lines[0].starts_with(r#"{"error":"Confirmation required","message":"'wipe' is destructive and cannot be confirmed here""#), "{}", lines[0]);
+    assert_eq!(r#"{"command":"wipe","outcome":"skipped"}"#, lines[1]);
+    assert_eq!("the number is 1", lines[2]);
+}
+
+#[test]
+fn run_args_yes() {
+    let commander = Commander::new(vec![Box::new(WipeParser)]);
+
+    let mut term = Mock::default();
+    let mut context = TestContext { state: 42 };
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::SUCCESS, looper.run_args(["--yes", "wipe"]).unwrap());
+    assert_eq!(0, context.state);
+    assert!(term.invocations().is_empty());
+
+    let mut term = Mock::default();
+    let mut context = TestContext { state: 42 };
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::SUCCESS, looper.run_args(["-y", "-c", "wipe"]).unwrap());
+    assert_eq!(0, context.state);
+
+    // the override only lasts for the duration of the call
+    let mut term = Mock::default().on_read_line(lines(&["n"]));
+    let mut context = TestContext { state: 42 };
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.run_args(["--yes", "wipe"]).unwrap();
+    looper.context().state = 42;
+    assert_eq!(ExitStatus::SUCCESS, looper.run_args(["wipe"]).unwrap());
+    assert_eq!(42, context.state);
+    assert_eq!(
+        &[
+            Print("Are you sure you want to run 'wipe'? [y/N] ".into(), Ok(())),
+            ReadLine(Ok("n".into())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn continuation_escaped() {
+    let mut term = Mock::default().on_read_line(lines(&["echo 1\\\n", "2\\", "3", "quit"]));
+    let commander = Commander::new(vec![Box::new(EchoParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context)
+        .with_continuation_check(default_continuation_check);
+    looper.run().unwrap();
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("echo 1\\\n".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Ok("2\\".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Ok("3".into())),
+            Print("the number is 123\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("quit".into())),
+            Print("Exiting.\n".into(), Ok(())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn continuation_unbalanced_then_invalid() {
+    let mut term = Mock::default().on_read_line(lines(&["echo (", ")", "quit"]));
+    let commander = Commander::new(vec![Box::new(EchoParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext { state: 7 };
+    let mut looper = Looper::new(&mut term, &commander, &mut context)
+        .with_prompt(&StatePrompt)
+        .with_continuation_check(default_continuation_check);
+    looper.run().unwrap();
+
+    assert_eq!(
+        &[
+            Print("[7|Applied]> ".into(), Ok(())),
+            ReadLine(Ok("echo (".into())),
+            Print("[7]. ".into(), Ok(())),
+            ReadLine(Ok(")".into())),
+            Print("Invalid input: invalid digit found in string.\n".into(), Ok(())),
+            Print("[7|Applied]> ".into(), Ok(())),
+            ReadLine(Ok("quit".into())),
+            Print("Exiting.\n".into(), Ok(())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn continuation_disabled_by_default() {
+    let mut term = Mock::default().on_read_line(lines(&["echo (", "quit"]));
+    let commander = Commander::new(vec![Box::new(EchoParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.run().unwrap();
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("echo (".into())),
+            Print("Invalid input: invalid digit found in string.\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("quit".into())),
+            Print("Exiting.\n".into(), Ok(())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn continuation_incomplete_parse() {
+    let mut term = Mock::default().on_read_line(lines(&["sum 1 2", "  3", "4;", "quit"]));
+    let commander = Commander::new(vec![Box::new(SumParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.run().unwrap();
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("sum 1 2".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Ok("  3".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Ok("4;".into())),
+            Print("the number is 10\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("quit".into())),
+            Print("Exiting.\n".into(), Ok(())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn continuation_unterminated() {
+    let mut term = Mock::default()
+        .with_interactive(false)
+        .on_read_line(lines(&["echo (1"]));
+    let commander = Commander::new(vec![Box::new(EchoParser), Box::new(SumParser)]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context)
+        .with_continuation_check(default_continuation_check);
+    assert_eq!(ExitStatus::FAILURE, looper.run().unwrap());
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("echo (1".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Err("end of input".into())),
+            Print("Invalid input: unterminated input.\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Err("end of input".into())),
+        ],
+        term.invocations()
+    );
+
+    let mut term = Mock::default()
+        .with_interactive(false)
+        .on_read_line(lines(&["sum 1", "2"]));
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::FAILURE, looper.run().unwrap());
+    assert_eq!(
+        Some(&Print("Invalid input: unterminated input.\n".into(), Ok(()))),
+        term.invocations().get(6)
+    );
+}
+
+#[test]
+fn continuation_incomplete_run_once() {
+    let mut term = Mock::default();
+    let commander = Commander::new(vec![Box::new(SumParser)]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::FAILURE, looper.run_once("sum 1").unwrap());
+
+    assert_eq!(
+        &[Print("Invalid input: missing ';'.\n".into(), Ok(()))],
+        term.invocations()
+    );
+}
+
+#[test]
+fn heredoc() {
+    let mut term = Mock::default().on_read_line(lines(&["publish news <<EOF", "{", "  \"a\": 1", "}", "EOF", "quit"]));
+    let commander = Commander::new(vec![Box::new(PublishParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context).with_heredoc(true);
+    looper.run().unwrap();
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("publish news <<EOF".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Ok("{".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Ok("  \"a\": 1".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Ok("}".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Ok("EOF".into())),
+            Print("published to news: \"{\\n  \\\"a\\\": 1\\n}\"\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("quit".into())),
+            Print("Exiting.\n".into(), Ok(())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn heredoc_rejected_by_parser() {
+    let mut term = Mock::default().on_read_line(lines(&["echo <<END", "1", "END", "quit"]));
+    let commander = Commander::new(vec![Box::new(EchoParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context).with_heredoc(true);
+    looper.run().unwrap();
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("echo <<END".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Ok("1".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Ok("END".into())),
+            Print("Invalid input: 'echo' does not accept a payload.\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("quit".into())),
+            Print("Exiting.\n".into(), Ok(())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn heredoc_unterminated() {
+    let mut term = Mock::default()
+        .with_interactive(false)
+        .on_read_line(lines(&["publish news <<EOF", "{"]));
+    let commander = Commander::new(vec![Box::new(PublishParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context).with_heredoc(true);
+    assert_eq!(ExitStatus::FAILURE, looper.run().unwrap());
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("publish news <<EOF".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Ok("{".into())),
+            Print("... ".into(), Ok(())),
+            ReadLine(Err("end of input".into())),
+            Print("Invalid input: unterminated heredoc; expected 'EOF'.\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Err("end of input".into())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn heredoc_disabled_by_default() {
+    let mut term = Mock::default().on_read_line(lines(&["publish news <<EOF", "quit"]));
+    let commander = Commander::new(vec![Box::new(PublishParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    looper.run().unwrap();
+
+    assert_eq!(
+        Print("published to news: \"<<EOF\"\n".into(), Ok(())),
+        term.invocations()[2]
+    );
+}
+
+#[test]
+fn heredoc_run_once() {
+    let commander = Commander::new(vec![Box::new(PublishParser)]);
+    let mut context = TestContext::default();
+
+    let mut term = Mock::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context).with_heredoc(true);
+    assert_eq!(ExitStatus::SUCCESS, looper.run_once("publish news <<EOF\r\nhello\r\nworld\r\nEOF\r\n").unwrap());
+    assert_eq!(
+        &[Print("published to news: \"hello\\nworld\"\n".into(), Ok(()))],
+        term.invocations()
+    );
+
+    let mut term = Mock::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context).with_heredoc(true);
+    assert_eq!(ExitStatus::FAILURE, looper.run_args(["-c", "publish news <<EOF\nhello"]).unwrap());
+    assert_eq!(
+        &[Print("Invalid input: unterminated heredoc; expected 'EOF'.\n".into(), Ok(()))],
+        term.invocations()
+    );
+}
+
+#[test]
+fn default_continuation_check_cases() {
+    use Continuation::{Complete, Escaped, Unbalanced};
+    assert_eq!(Complete, default_continuation_check(""));
+    assert_eq!(Complete, default_continuation_check("echo 1"));
+    assert_eq!(Escaped, default_continuation_check("echo \\"));
+    assert_eq!(Complete, default_continuation_check("echo \\\\"));
+    assert_eq!(Escaped, default_continuation_check("echo \\\\\\"));
+    assert_eq!(Unbalanced, default_continuation_check("say \"hello"));
+    assert_eq!(Complete, default_continuation_check("say \"hello\""));
+    assert_eq!(Complete, default_continuation_check("say \"a \\\" b\""));
+    assert_eq!(Unbalanced, default_continuation_check("say 'it"));
+    assert_eq!(Complete, default_continuation_check("say 'a \\'"));
+    assert_eq!(Complete, default_continuation_check("say \"it's\""));
+    assert_eq!(Unbalanced, default_continuation_check("post {\"a\": [1, 2"));
+    assert_eq!(Unbalanced, default_continuation_check("post {\"a\": [1, 2]"));
+    assert_eq!(Complete, default_continuation_check("post {\"a\": [1, 2]}"));
+    assert_eq!(Complete, default_continuation_check("post {\"}\": \"{\"}"));
+    assert_eq!(Complete, default_continuation_check("select (a))"));
+    assert_eq!(Complete, default_continuation_check("echo \\("));
+}
+
+#[test]
+fn command_line_from_args() {
+    assert_eq!(None, command_line(Vec::<&str>::new()).unwrap());
+    assert_eq!(Some("add 1.5".into()), command_line(["-c", "add 1.5"]).unwrap());
+    assert_eq!(Some("add 1.5".into()), command_line(["add", "1.5"]).unwrap());
+    assert_eq!(
+        Some(r#"publish hello world say "hi" a\b c"#.into()),
+        command_line(["publish", "hello world", r#"say "hi""#, r"a\b c"]).unwrap()
+    );
+    assert!(command_line(["-c"]).is_err());
+    assert!(command_line(["-c", "add 1", "extra"]).is_err());
+}
+
+struct StatePrompt;
+
+impl Prompt<TestContext> for StatePrompt {
+    fn render(&self, outcome: LastCommandOutcome, context: &TestContext) -> String {
+        format!("[{}|{outcome:?}]> ", context.state)
+    }
+
+    fn render_continuation(&self, context: &TestContext) -> String {
+        format!("[{}]. ", context.state)
+    }
+}
+
+#[test]
+fn custom_prompt() {
+    let mut term = Mock::default().on_read_line(lines(&["exit 3", "respond", "quit"]));
+    let commander = Commander::new(vec![
+        Box::new(ExitParser),
+        Box::new(RespondParser {
+            val: Ok(ApplyOutcome::Skipped),
+        }),
+        Box::new(quit::Parser::default()),
+    ]);
+    let mut context = TestContext { state: 42 };
+    let mut looper = Looper::new(&mut term, &commander, &mut context).with_prompt(&StatePrompt);
+    looper.run().unwrap();
+
+    assert_eq!(
+        &[
+            Print("[42|Applied]> ".into(), Ok(())),
+            ReadLine(Ok("exit 3".into())),
+            Print("[42|Applied]> ".into(), Ok(())),
+            ReadLine(Ok("respond".into())),
+            Print("[42|Skipped]> ".into(), Ok(())),
+            ReadLine(Ok("quit".into())),
+            Print("Exiting.\n".into(), Ok(())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn closure_prompt() {
+    let env = String::from("prod");
+    let mut term = Mock::default().on_read_line(lines(&["quit"]));
+    let commander = Commander::new(vec![Box::new(quit::Parser::<_, TestError>::default())]);
+    let mut context = TestContext { state: 7 };
+    let prompt = |_, context: &TestContext| format!("{env}:{}> ", context.state);
+    let mut looper = Looper::new(&mut term, &commander, &mut context).with_prompt(&prompt);
+    looper.run().unwrap();
+
+    assert_eq!(Print("prod:7> ".into(), Ok(())), term.invocations()[0]);
+}
+
+#[test]
+fn default_prompt() {
+    let prompt = |outcome| Prompt::<()>::render(&DefaultPrompt, outcome, &());
+    assert_eq!("+>> ", prompt(LastCommandOutcome::Applied));
+    assert_eq!("->> ", prompt(LastCommandOutcome::Skipped));
+    assert_eq!("!>> ", prompt(LastCommandOutcome::Erred));
+}
+
+struct HintingPresenter;
+
+impl ErrorPresenter<TestError> for HintingPresenter {
+    fn command_error(&self, err: &TestError) -> Report {
+        Report::new("Failed", err).with_hint("try again")
+    }
+
+    fn invalid_input(&self, _: &str, err: &ParseCommandError) -> Report {
+        Report::new("Huh", err).with_hint("type 'help'")
+    }
+}
+
+#[test]
+fn custom_error_presenter() {
+    let mut term = Mock::default().on_read_line(lines(&["bogus", "respond", "quit"]));
+    let commander = Commander::new(vec![
+        Box::new(RespondParser {
+            val: Err(ApplyCommandError::Application(TestError("cooling pump exploded".into()))),
+        }),
+        Box::new(quit::Parser::default()),
+    ]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context).with_error_presenter(&HintingPresenter);
+    looper.run().unwrap();
+
+    assert_eq!(
+        &[
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("bogus".into())),
+            Print("Huh: no command parser for 'bogus'.\n".into(), Ok(())),
+            Print("    hint: type 'help'\n".into(), Ok(())),
+            Print("+>> ".into(), Ok(())),
+            ReadLine(Ok("respond".into())),
+            Print("Failed: cooling pump exploded.\n".into(), Ok(())),
+            Print("    hint: try again\n".into(), Ok(())),
+            Print("!>> ".into(), Ok(())),
+            ReadLine(Ok("quit".into())),
+            Print("Exiting.\n".into(), Ok(())),
+        ],
+        term.invocations()
+    );
+}
+
+#[test]
+fn source_chain_error_presenter() {
+    let report = SourceChainErrorPresenter.command_error(&ChainedError(
+        "cooling failed".into(),
+        TestError("pump exploded".into()),
+    ));
+    assert_eq!(Report::new("Command error", "cooling failed").with_cause("pump exploded"), report);
+}
+
+#[test]
+fn command_error_ansi() {
+    let mut term = Mock::default().with_ansi(true).on_read_line(lines(&["respond", "quit"]));
+    let commander = Commander::new(vec![
+        Box::new(RespondParser {
+            val: Err(ApplyCommandError::Application(TestError("cooling pump exploded".into()))),
+        }),
+        Box::new(quit::Parser::default()),
+    ]);
+    let mut context = TestContext::default();
+    Looper::new(&mut term, &commander, &mut context).run().unwrap();
+
+    assert_eq!(
+        Print("\x1b[1;31mCommand error: cooling pump exploded.\x1b[0m\n".into(), Ok(())),
+        term.invocations()[2]
+    );
+}
+
+#[test]
+fn run_flag_implements_debug() {
+    let flag = RunFlag::Running;
+    assert_eq!("Running", format!("{flag:?}"));
+}
+
+#[test]
+fn output_format_text() {
+    let mut term = Mock::default().on_read_line(lines(&["measure 42", "quit"]));
+    let commander = Commander::new(vec![Box::new(MeasureParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+    Looper::new(&mut term, &commander, &mut context).run().unwrap();
+
+    assert_eq!("+>> number: 42\ndigits: 2\n+>> Exiting.\n", term.output());
+}
+
+#[test]
+fn output_format_json() {
+    let mut term = Mock::default()
+        .with_output_format(OutputFormat::Json)
+        .on_read_line(lines(&["measure 42", "measure x", "respond", "quit"]));
+    let commander = Commander::new(vec![
+        Box::new(MeasureParser),
+        Box::new(RespondParser {
+            val: Err(ApplyCommandError::Application(TestError("cooling pump exploded".into()))),
+        }),
+        Box::new(quit::Parser::default()),
+    ]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::FAILURE, looper.run().unwrap());
+
+    assert_eq!(concat!(
+        r#"{"number":"42","digits":2}"#, "\n",
+        r#"{"command":"measure 42","outcome":"applied"}"#, "\n",
+        r#"{"error":"Invalid input","message":"invalid digit found in string","snippet":null,"causes":[],"hint":null}"#, "\n",
+        r#"{"command":"measure x","outcome":"erred"}"#, "\n",
+        r#"{"error":"Command error","message":"cooling pump exploded","snippet":null,"causes":[],"hint":null}"#, "\n",
+        r#"{"command":"respond","outcome":"erred"}"#, "\n",
+        r#"{"command":"quit","outcome":"applied"}"#, "\n",
+    ), term.output());
+}
+
+#[test]
+fn output_format_json_run_once() {
+    let commander = Commander::new(vec![Box::new(MeasureParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+
+    let mut term = Mock::default().with_output_format(OutputFormat::Json);
+    Looper::new(&mut term, &commander, &mut context).run_once("measure 7").unwrap();
+    assert_eq!(concat!(
+        r#"{"number":"7","digits":1}"#, "\n",
+        r#"{"command":"measure 7","outcome":"applied"}"#, "\n",
+    ), term.output());
+
+    let mut term = Mock::default().with_output_format(OutputFormat::Json);
+    let status = Looper::new(&mut term, &commander, &mut context).run_once("zap").unwrap();
+    assert_eq!(ExitStatus::FAILURE, status);
+    assert_eq!(concat!(
+        r#"{"error":"Invalid input","message":"no command parser for 'zap'","snippet":{"line":"zap","start":0,"end":3},"causes":[],"hint":null}"#, "\n",
+        r#"{"command":"zap","outcome":"erred"}"#, "\n",
+    ), term.output());
+}
+
+#[test]
+fn output_format_csv() {
+    let mut term = Mock::default()
+        .with_output_format(OutputFormat::Csv)
+        .with_interactive(false)
+        .on_read_line(lines(&["measure 42", "measure x", "quit"]));
+    let commander = Commander::new(vec![Box::new(MeasureParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+    let mut looper = Looper::new(&mut term, &commander, &mut context);
+    assert_eq!(ExitStatus::FAILURE, looper.run().unwrap());
+
+    assert_eq!(concat!(
+        "42,2\n",
+        "measure 42,applied\n",
+        "Invalid input,invalid digit found in string,,[],\n",
+        "measure x,erred\n",
+        "quit,applied\n",
+    ), term.output());
+}
+
+#[test]
+fn deprecated_command_warns() {
+    let commander = Commander::new(vec![Box::new(ClearParser), Box::new(WipeParser)]);
+    let mut context = TestContext { state: 5 };
+
+    let mut term = Mock::default();
+    let status = Looper::new(&mut term, &commander, &mut context).run_once("clear").unwrap();
+    assert_eq!(ExitStatus::SUCCESS, status);
+    assert_eq!(0, context.state);
+    assert_eq!("Warning: 'clear' is deprecated; use 'wipe' instead.\n", term.output());
+
+    let mut term = Mock::default().with_output_format(OutputFormat::Json);
+    Looper::new(&mut term, &commander, &mut context).run_once("clear").unwrap();
+    assert_eq!(concat!(
+        r#"{"warning":"'clear' is deprecated; use 'wipe' instead"}"#, "\n",
+        r#"{"command":"clear","outcome":"applied"}"#, "\n",
+    ), term.output());
+}
+
+#[test]
+fn relaxed_matching() {
+    let commander = Commander::new(vec![Box::new(WipeParser), Box::new(quit::Parser::default())])
+        .with_case_insensitive(true)
+        .with_prefix_matching(true);
+    let mut context = TestContext { state: 42 };
+
+    let mut term = Mock::default().on_read_line(lines(&["n"]));
+    Looper::new(&mut term, &commander, &mut context).run_once("WI").unwrap();
+    assert_eq!(42, context.state);
+    assert_eq!("Are you sure you want to run 'wipe'? [y/N] ", term.output());
+
+    let mut term = Mock::default();
+    let status = Looper::new(&mut term, &commander, &mut context).run_once("Qui 3").unwrap();
+    assert_eq!(ExitStatus(3), status);
+}
+
+#[test]
+fn parse_error_sets_failure_when_not_interactive() {
+    let commander = Commander::new(vec![Box::new(EchoParser), Box::new(quit::Parser::default())]);
+    let mut context = TestContext::default();
+
+    let mut term = Mock::default()
+        .with_interactive(false)
+        .on_read_line(lines(&["echo x", "echo 2", "quit"]));
+    assert_eq!(ExitStatus::FAILURE, Looper::new(&mut term, &commander, &mut context).run().unwrap());
+
+    let mut term = Mock::default()
+        .with_interactive(false)
+        .on_read_line(lines(&["zap", "quit 3"]));
+    assert_eq!(ExitStatus(3), Looper::new(&mut term, &commander, &mut context).run().unwrap());
+
+    // an interactive user simply corrects the input
+    let mut term = Mock::default().on_read_line(lines(&["echo x", "echo 2", "quit"]));
+    assert_eq!(ExitStatus::SUCCESS, Looper::new(&mut term, &commander, &mut context).run().unwrap());
 }
@@ -1,19 +1,162 @@
 //! An abstract, text-based interface with the user. This module fulfils the 'read' and
 //! 'print' parts of a REPL application.
 
+mod format;
 mod mock;
+mod pager;
+mod progress;
+mod report;
+mod secret;
+mod size;
 mod streaming;
+mod style;
+mod table;
+mod tty;
 
+pub use format::*;
 pub use mock::*;
+pub use pager::*;
+pub use progress::*;
+pub use report::*;
+pub use secret::*;
+pub use size::*;
 pub use streaming::*;
+pub use style::*;
+pub use table::*;
 
+use std::collections::BTreeSet;
 use std::fmt::{Display};
+use std::io;
 use std::str::FromStr;
+use std::sync::Arc;
 use thiserror::Error;
 
-#[derive(Debug, Clone, PartialEq, Eq, Error)]
-#[error("{0}")]
-pub struct AccessTerminalError(pub String);
+/// The category of an [`AccessTerminalError`], allowing callers to distinguish between errors that
+/// signal a normal end of interaction (such as [`AccessTerminalErrorKind::EndOfInput`]) and
+/// genuine failures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccessTerminalErrorKind {
+    /// The input stream has been exhausted; e.g., the user pressed Ctrl-D or the end of a piped
+    /// file was reached.
+    EndOfInput,
+
+    /// The operation was interrupted by the user; e.g., by pressing Ctrl-C.
+    Interrupted,
+
+    /// The other end of the stream has been closed.
+    BrokenPipe,
+
+    /// The input could not be decoded; e.g., it was not valid UTF-8.
+    Encoding,
+
+    /// Some other I/O error, retaining the original [`io::ErrorKind`].
+    Io(io::ErrorKind),
+
+    /// An error that did not originate from an I/O operation.
+    Other,
+}
+
+impl From<io::ErrorKind> for AccessTerminalErrorKind {
+    fn from(kind: io::ErrorKind) -> Self {
+        match kind {
+            io::ErrorKind::UnexpectedEof => Self::EndOfInput,
+            io::ErrorKind::Interrupted => Self::Interrupted,
+            io::ErrorKind::BrokenPipe => Self::BrokenPipe,
+            io::ErrorKind::InvalidData => Self::Encoding,
+            kind => Self::Io(kind),
+        }
+    }
+}
+
+/// Produced when the terminal device could not be accessed for reading or writing. An error
+/// converted from an [`io::Error`] takes its message from the [`io::Error`], which is retained as
+/// the source.
+///
+/// Two errors are considered equal if they have the same kind and message; the source is not compared.
+///
+/// This was formerly a tuple struct, `AccessTerminalError(pub String)`. In place of that
+/// constructor, use [`AccessTerminalError::other`] (or convert from a [`String`] or a string slice);
+/// in place of the field, use [`AccessTerminalError::message`].
+#[derive(Debug, Clone, Error)]
+#[error("{message}")]
+pub struct AccessTerminalError {
+    kind: AccessTerminalErrorKind,
+    message: String,
+    #[source]
+    source: Option<Arc<io::Error>>,
+}
+
+impl AccessTerminalError {
+    /// Creates a new error of the given kind, with no source.
+    pub fn new(kind: AccessTerminalErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Creates a new error of the [`AccessTerminalErrorKind::Other`] kind.
+    pub fn other(message: impl Into<String>) -> Self {
+        Self::new(AccessTerminalErrorKind::Other, message)
+    }
+
+    /// The input stream has been exhausted; e.g., the user pressed Ctrl-D or the end of a piped
+    /// file was reached.
+    pub fn end_of_input() -> Self {
+        Self::new(AccessTerminalErrorKind::EndOfInput, "end of input")
+    }
+
+    /// The read was interrupted by the user; e.g., by pressing Ctrl-C.
+    pub fn interrupted() -> Self {
+        Self::new(AccessTerminalErrorKind::Interrupted, "interrupted")
+    }
+
+    /// The category of the error.
+    pub fn kind(&self) -> AccessTerminalErrorKind {
+        self.kind
+    }
+
+    /// The error message, excluding the source.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// The kind of the underlying [`io::Error`], if the error originated from an I/O operation.
+    pub fn io_kind(&self) -> Option<io::ErrorKind> {
+        self.source.as_ref().map(|err| err.kind())
+    }
+}
+
+impl PartialEq for AccessTerminalError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.message == other.message
+    }
+}
+
+impl Eq for AccessTerminalError {}
+
+impl From<io::Error> for AccessTerminalError {
+    fn from(err: io::Error) -> Self {
+        Self {
+            kind: err.kind().into(),
+            message: err.to_string(),
+            source: Some(Arc::new(err)),
+        }
+    }
+}
+
+impl From<String> for AccessTerminalError {
+    fn from(message: String) -> Self {
+        Self::other(message)
+    }
+}
+
+impl From<&str> for AccessTerminalError {
+    fn from(message: &str) -> Self {
+        Self::other(message)
+    }
+}
 
 /// Specification of a text-based I/O device for interfacing with the user. Ordinarily, this is a
 /// terminal utilising `stdin` and `stdout` devices; however, the separation of a concrete terminal
@@ -35,6 +178,192 @@ pub trait Terminal {
         self.print(&buf)
     }
 
+    /// Prints a string slice that may be too long to fit on the screen; e.g., a listing. An
+    /// interactive terminal may present it in a [`Pager`] if it exceeds the terminal height.
+    /// The default implementation prints it as-is.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    fn page(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        self.print(s)
+    }
+
+    /// Whether the terminal delivers individual key presses (see [`Self::read_key`]); e.g., an
+    /// interactive terminal. The default implementation returns `false`.
+    fn supports_keys(&self) -> bool {
+        false
+    }
+
+    /// Reads a single key press, as the bytes delivered by a terminal in raw mode (e.g., `\x1b[A`
+    /// for the up arrow), blocking until one is available. The default implementation returns an
+    /// error, as the terminal does not [support key presses](Self::supports_keys).
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for reading, or does not deliver key presses.
+    fn read_key(&mut self) -> Result<Vec<u8>, AccessTerminalError> {
+        Err(AccessTerminalError::other("key presses are not supported"))
+    }
+
+    /// Whether the terminal renders ANSI escape sequences. When `false`, styled output degrades
+    /// to plain text. The default implementation returns `false`.
+    fn supports_ansi(&self) -> bool {
+        false
+    }
+
+    /// The dimensions of the terminal, for adapting the layout of output (e.g., wrapping long
+    /// lines). The default implementation returns [`TerminalSize::default`].
+    fn size(&self) -> TerminalSize {
+        TerminalSize::default()
+    }
+
+    /// The format in which structured output, such as a [`Table`], is rendered. The default
+    /// implementation returns [`OutputFormat::Text`].
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Text
+    }
+
+    /// Prints a [`Table`] in the [`OutputFormat`] of the terminal. As text, the table is fitted
+    /// to the width of the terminal and printed with [`Self::page`]; machine-readable formats are
+    /// printed as-is.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    fn print_table(&mut self, table: &Table) -> Result<(), AccessTerminalError> {
+        let format = self.output_format();
+        let rendered = table.render(format, self.size(), self.supports_ansi());
+        if format.is_machine_readable() {
+            self.print(&rendered)
+        } else {
+            self.page(&rendered)
+        }
+    }
+
+    /// Whether the terminal can redraw a status line in place (see [`Self::set_status`]). The
+    /// default implementation returns `false`.
+    fn supports_redraw(&self) -> bool {
+        false
+    }
+
+    /// Shows a transient status line; e.g., the state of a [`ProgressBar`] or a [`Spinner`]. On a
+    /// terminal that [supports redrawing](Self::supports_redraw), the status line is redrawn in
+    /// place, replacing the previous status, and is cleared by any other output. The default
+    /// implementation prints the status as an ordinary line.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    fn set_status(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        self.print_line(s)
+    }
+
+    /// Clears the status line, if one is shown. The default implementation does nothing.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    fn clear_status(&mut self) -> Result<(), AccessTerminalError> {
+        Ok(())
+    }
+
+    /// Creates a [`ProgressBar`] over this terminal, for the given amount of work.
+    fn progress_bar(&mut self, label: &str, total: u64) -> ProgressBar<'_, Self>
+    where
+        Self: Sized,
+    {
+        ProgressBar::new(self, label, total)
+    }
+
+    /// Creates a [`Spinner`] over this terminal.
+    fn spinner(&mut self, label: &str) -> Spinner<'_, Self>
+    where
+        Self: Sized,
+    {
+        Spinner::new(self, label)
+    }
+
+    /// Emits a structured [`Value`], rendered in the [`OutputFormat`] of the terminal: as a single
+    /// line of JSON, as a single CSV record (see [`Value::to_csv`]), or as human-readable text.
+    /// Nothing is printed for a value that renders as empty text or an empty record (e.g., an
+    /// empty object).
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    fn emit(&mut self, value: &Value) -> Result<(), AccessTerminalError> {
+        let rendered = match self.output_format() {
+            OutputFormat::Text => value.to_string(),
+            OutputFormat::Csv => value.to_csv(),
+            OutputFormat::Json => return self.print_line(&value.to_json()),
+        };
+        if rendered.is_empty() {
+            Ok(())
+        } else {
+            self.print_line(&rendered)
+        }
+    }
+
+    /// Prints a string slice in the given [`Style`], or as plain text if the terminal does
+    /// not support ANSI styling.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    fn print_styled(&mut self, s: &str, style: Style) -> Result<(), AccessTerminalError> {
+        if self.supports_ansi() {
+            self.print(&style.paint(s))
+        } else {
+            self.print(s)
+        }
+    }
+
+    /// A variation of [`Self::print_styled`] with an added trailing newline separator.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    fn print_line_styled(&mut self, s: &str, style: Style) -> Result<(), AccessTerminalError> {
+        if self.supports_ansi() {
+            self.print_line(&style.paint(s))
+        } else {
+            self.print_line(s)
+        }
+    }
+
+    /// Prints a line in the [`Style`] of the given [`Level`].
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    fn print_level(&mut self, level: Level, s: &str) -> Result<(), AccessTerminalError> {
+        self.print_line_styled(s, level.style())
+    }
+
+    /// Prints a line at the [`Level::Info`] level.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    fn print_info(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        self.print_level(Level::Info, s)
+    }
+
+    /// Prints a line at the [`Level::Warning`] level.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    fn print_warning(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        self.print_level(Level::Warning, s)
+    }
+
+    /// Prints a line at the [`Level::Error`] level.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    fn print_error(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        self.print_level(Level::Error, s)
+    }
+
+    /// Prints a line at the [`Level::Success`] level.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    fn print_success(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        self.print_level(Level::Success, s)
+    }
+
     /// Reads a complete line from the input device, blocking until the input becomes
     /// available for consumption.
     ///
@@ -42,6 +371,24 @@ pub trait Terminal {
     /// If the terminal device could not be accessed for reading.
     fn read_line(&mut self) -> Result<String, AccessTerminalError>;
 
+    /// Reads a sensitive value, such as a password or a token, from the input device. The value is
+    /// returned as a [`Secret`], which redacts itself when formatted.
+    ///
+    /// Implementations should avoid echoing the input and recording the value. The default
+    /// implementation cannot do so; it delegates to [`Self::read_line`], stripping the line terminator.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for reading.
+    fn read_secret(&mut self) -> Result<Secret, AccessTerminalError> {
+        self.read_line().map(Secret::from_line)
+    }
+
+    /// Whether the input is typed by a user, who can answer questions such as [`Self::confirm`],
+    /// rather than piped or read from a script. The default implementation returns `true`.
+    fn is_interactive(&self) -> bool {
+        true
+    }
+
     /// A variation of [`Self::read_from_str`] that operates on any type `V` that also implements the
     /// [`Default`] trait. The default value is returned when an empty (comprising only whitespace
     /// characters) input line is submitted by the user.
@@ -76,9 +423,90 @@ pub trait Terminal {
         self.read_value(prompt, FromStr::from_str)
     }
 
+    /// Asks the user a yes/no question, returning `true` if the answer was "yes". The answer is
+    /// case-insensitive: `y`, `yes`, `n` and `no` are accepted. An empty answer selects the `default`,
+    /// if one is given; otherwise, the user is asked again. The question is followed by a `[Y/n]`,
+    /// `[y/N]` or `[y/n]` suffix, depending on the default.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for reading or writing.
+    fn confirm(&mut self, question: &str, default: Option<bool>) -> Result<bool, AccessTerminalError> {
+        let suffix = match default {
+            Some(true) => "[Y/n]",
+            Some(false) => "[y/N]",
+            None => "[y/n]",
+        };
+        self.read_value(&format!("{question} {suffix} "), |str| parse_yes_no(str, default))
+    }
+
+    /// Asks the user to pick one of the given `items`, returning a reference to the chosen item.
+    ///
+    /// On a terminal that [supports key presses](Self::supports_keys), the prompt is followed by
+    /// the list of items, through which the user moves with the arrow keys (or `j` and `k`),
+    /// picking the item under the cursor with Enter; the list is then replaced by the answer.
+    /// Otherwise, a numbered fallback works on any stream: the items are printed as a numbered
+    /// list, followed by the prompt, and the user is asked repeatedly until a valid item number is
+    /// entered.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for reading or writing, or the user pressed
+    /// Ctrl-C while navigating the list.
+    ///
+    /// # Panics
+    /// If `items` is empty.
+    fn select<'i, V: Display>(&mut self, prompt: &str, items: &'i [V]) -> Result<&'i V, AccessTerminalError> {
+        assert!(!items.is_empty(), "no items to select from");
+        if self.supports_keys() {
+            let (cursor, _) = navigate_menu(self, prompt, items, false)?;
+            return Ok(&items[cursor]);
+        }
+        print_menu(self, items)?;
+        let hint = match items.len() {
+            1 => "[1]".into(),
+            count => format!("[1-{count}]"),
+        };
+        let index = self.read_value(&format!("{prompt} {hint} "), |str| {
+            parse_item_number(str, items.len())
+        })?;
+        Ok(&items[index])
+    }
+
+    /// Asks the user to pick any number of the given `items`, returning references to the chosen
+    /// items in the order they appear in the list.
+    ///
+    /// On a terminal that [supports key presses](Self::supports_keys), the user moves through the
+    /// list as per [`Self::select`], toggles the item under the cursor with Space, and confirms
+    /// the choice with Enter. Otherwise, in the numbered fallback, the user enters item numbers
+    /// and ranges separated by commas or spaces (e.g., `1,3-5`); duplicates are ignored. Either
+    /// way, the user may select nothing.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for reading or writing, or the user pressed
+    /// Ctrl-C while navigating the list.
+    ///
+    /// # Panics
+    /// If `items` is empty.
+    fn select_many<'i, V: Display>(&mut self, prompt: &str, items: &'i [V]) -> Result<Vec<&'i V>, AccessTerminalError> {
+        assert!(!items.is_empty(), "no items to select from");
+        if self.supports_keys() {
+            let (_, chosen) = navigate_menu(self, prompt, items, true)?;
+            return Ok(items.iter().zip(chosen).filter_map(|(item, chosen)| chosen.then_some(item)).collect());
+        }
+        print_menu(self, items)?;
+        let hint = match items.len() {
+            1 => "[e.g., 1]".into(),
+            2 => "[e.g., 1,2]".into(),
+            count => format!("[e.g., 1,2-{count}]"),
+        };
+        let indexes = self.read_value(&format!("{prompt} {hint} "), |str| {
+            parse_item_numbers(str, items.len())
+        })?;
+        Ok(indexes.into_iter().map(|index| &items[index]).collect())
+    }
+
     /// Reads a value from a terminal device using the supplied `parser` function. The user is prompted
     /// repeatedly until the parser yields a non-error value. (The parser error is never propagated to
-    /// the caller.)
+    /// the caller; it is printed as an `Invalid input` [`Report`].)
     ///
     /// # Errors
     /// If the terminal device could not be accessed for reading or writing.
@@ -94,12 +522,160 @@ pub trait Terminal {
             match parsed {
                 Ok(val) => return Ok(val),
                 Err(err) => {
-                    self.print_line(&format!("Invalid input: {err}."))?;
+                    Report::new("Invalid input", err).print(self)?;
                 }
             }
         }
     }
 }
 
+/// Parses a yes/no answer for [`Terminal::confirm`].
+fn parse_yes_no(s: &str, default: Option<bool>) -> Result<bool, &'static str> {
+    match s.to_lowercase().as_str() {
+        "y" | "yes" => Ok(true),
+        "n" | "no" => Ok(false),
+        "" => default.ok_or("an answer is required"),
+        _ => Err("expected 'y' or 'n'"),
+    }
+}
+
+/// Prints the numbered list of items for [`Terminal::select`] and [`Terminal::select_many`].
+fn print_menu<V: Display>(terminal: &mut (impl Terminal + ?Sized), items: &[V]) -> Result<(), AccessTerminalError> {
+    let width = items.len().to_string().len();
+    for (index, item) in items.iter().enumerate() {
+        terminal.print_line(&format!("  {:>width$}) {item}", index + 1))?;
+    }
+    Ok(())
+}
+
+/// A key press recognised while navigating a menu in [`Terminal::select`] or
+/// [`Terminal::select_many`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MenuKey {
+    Up,
+    Down,
+    Top,
+    Bottom,
+    Toggle,
+    Confirm,
+    Interrupt,
+    Other,
+}
+
+impl MenuKey {
+    /// Decodes a key press from the bytes read with [`Terminal::read_key`].
+    fn decode(bytes: &[u8]) -> Self {
+        match bytes {
+            b"k" | b"\x1b[A" | b"\x1bOA" => Self::Up,
+            b"j" | b"\x1b[B" | b"\x1bOB" => Self::Down,
+            b"g" | b"\x1b[H" | b"\x1b[1~" => Self::Top,
+            b"G" | b"\x1b[F" | b"\x1b[4~" => Self::Bottom,
+            b" " => Self::Toggle,
+            b"\r" | b"\n" => Self::Confirm,
+            b"\x03" => Self::Interrupt,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Lets the user navigate the items with key presses, for [`Terminal::select`] (picking the item
+/// under the cursor) and [`Terminal::select_many`] (toggling items if `many` is set). The list is
+/// redrawn in place after each key press, and replaced by the answer once the user presses Enter
+/// (or erased if the user presses Ctrl-C).
+/// Returns the index of the item under the cursor, and whether each item was toggled on.
+fn navigate_menu<V: Display>(
+    terminal: &mut (impl Terminal + ?Sized),
+    prompt: &str,
+    items: &[V],
+    many: bool,
+) -> Result<(usize, Vec<bool>), AccessTerminalError> {
+    let hint = if many {
+        "(up/down: move, space: toggle, enter: confirm)"
+    } else {
+        "(up/down: move, enter: select)"
+    };
+    terminal.print_line(&format!("{prompt} {hint}"))?;
+    // items are truncated to fit on one line after the pointer (and checkbox), as a wrapped line
+    // would throw the redraw off
+    let prefix = if many { "> [ ] ".len() } else { "> ".len() };
+    let width = terminal.size().columns.saturating_sub(prefix);
+    let items = items
+        .iter()
+        .map(|item| item.to_string().chars().take(width).collect::<String>())
+        .collect::<Vec<_>>();
+    let (mut cursor, mut chosen) = (0, vec![false; items.len()]);
+    let mut redraw = false;
+    loop {
+        let mut menu = if redraw { format!("\x1b[{}A", items.len()) } else { String::new() };
+        for (index, item) in items.iter().enumerate() {
+            let pointer = if index == cursor { '>' } else { ' ' };
+            let line = match (many, chosen[index]) {
+                (false, _) => format!("{pointer} {item}"),
+                (true, false) => format!("{pointer} [ ] {item}"),
+                (true, true) => format!("{pointer} [x] {item}"),
+            };
+            menu.push_str("\r\x1b[2K");
+            if index == cursor && terminal.supports_ansi() {
+                menu.push_str(&Style::default().bold().paint(&line));
+            } else {
+                menu.push_str(&line);
+            }
+            menu.push('\n');
+        }
+        terminal.print(&menu)?;
+        redraw = true;
+
+        match MenuKey::decode(&terminal.read_key()?) {
+            MenuKey::Up => cursor = cursor.saturating_sub(1),
+            MenuKey::Down => cursor = (cursor + 1).min(items.len() - 1),
+            MenuKey::Top => cursor = 0,
+            MenuKey::Bottom => cursor = items.len() - 1,
+            MenuKey::Toggle if many => chosen[cursor] = !chosen[cursor],
+            MenuKey::Confirm => break,
+            MenuKey::Interrupt => {
+                terminal.print(&format!("\x1b[{}A\r\x1b[J", items.len() + 1))?;
+                return Err(AccessTerminalError::interrupted());
+            }
+            MenuKey::Toggle | MenuKey::Other => {}
+        }
+    }
+
+    let answer = if many {
+        items
+            .iter()
+            .zip(&chosen)
+            .filter_map(|(item, &chosen)| chosen.then_some(item.as_str()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    } else {
+        items[cursor].clone()
+    };
+    terminal.print(&format!("\x1b[{}A\r\x1b[J", items.len() + 1))?;
+    terminal.print_line(format!("{prompt} {answer}").trim_end())?;
+    Ok((cursor, chosen))
+}
+
+/// Parses a one-based item number into a zero-based index, given the number of items.
+fn parse_item_number(s: &str, count: usize) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(number) if (1..=count).contains(&number) => Ok(number - 1),
+        _ => Err(format!("expected a number between 1 and {count}, got '{s}'")),
+    }
+}
+
+/// Parses a list of item numbers and ranges into sorted, zero-based indexes, given the number of items.
+fn parse_item_numbers(s: &str, count: usize) -> Result<Vec<usize>, String> {
+    let mut indexes = BTreeSet::new();
+    for part in s.split(|ch: char| ch == ',' || ch.is_whitespace()).filter(|part| !part.is_empty()) {
+        let (start, end) = part.split_once('-').unwrap_or((part, part));
+        let (start, end) = (parse_item_number(start, count)?, parse_item_number(end, count)?);
+        if start > end {
+            return Err(format!("invalid range '{part}'"));
+        }
+        indexes.extend(start..=end);
+    }
+    Ok(indexes.into_iter().collect())
+}
+
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,309 @@
+//! Output formats: human-readable text for interactive use, and machine-readable formats for
+//! automation. Structured output is expressed as a [`Value`], which is rendered in either.
+
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter, Write};
+use std::str::FromStr;
+
+/// The format in which structured output, such as a [`Table`](crate::terminal::Table) or a
+/// [`Value`], is rendered. A terminal reports its format with
+/// [`Terminal::output_format`](crate::terminal::Terminal::output_format); the format applies to the
+/// whole session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Human-readable text, laid out to fit the terminal.
+    #[default]
+    Text,
+
+    /// Comma-separated values, as per RFC 4180.
+    Csv,
+
+    /// JSON, with one value per line (i.e., JSON Lines).
+    Json,
+}
+
+impl OutputFormat {
+    /// Is this a machine-readable format?
+    pub fn is_machine_readable(self) -> bool {
+        self != Self::Text
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Text => "text",
+            Self::Csv => "csv",
+            Self::Json => "json",
+        })
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    /// Parses `text`, `csv` or `json`, ignoring case; e.g., the value of a `--format` option.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("unsupported output format '{s}'; expected one of text, csv or json")),
+        }
+    }
+}
+
+/// A structured value, emitted with [`Terminal::emit`](crate::terminal::Terminal::emit). A value is
+/// rendered as a single line of JSON or a single CSV record in the corresponding [`OutputFormat`],
+/// or as human-readable text (via [`Display`]) otherwise.
+///
+/// As text, scalars are rendered plainly ([`Value::Null`] being empty), an array of scalars is
+/// rendered as a comma-separated list, and the members of an object are rendered as `key: value`
+/// lines, with nested arrays and objects indented beneath their key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<Value>),
+
+    /// An object, with its members in order of insertion.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Creates an object from the given members.
+    pub fn object<K: Into<String>>(members: impl IntoIterator<Item = (K, Value)>) -> Self {
+        Self::Object(members.into_iter().map(|(key, value)| (key.into(), value)).collect())
+    }
+
+    /// Renders the value as compact JSON, on a single line. Non-finite floats are rendered as
+    /// `null`.
+    pub fn to_json(&self) -> String {
+        let mut buf = String::new();
+        self.write_json(&mut buf);
+        buf
+    }
+
+    fn write_json(&self, buf: &mut String) {
+        match self {
+            Self::Null => buf.push_str("null"),
+            Self::Bool(value) => buf.push_str(if *value { "true" } else { "false" }),
+            Self::Integer(value) => buf.push_str(&value.to_string()),
+            Self::Float(value) if value.is_finite() => buf.push_str(&value.to_string()),
+            Self::Float(_) => Self::Null.write_json(buf),
+            Self::String(value) => buf.push_str(&json_string(value)),
+            Self::Array(items) => {
+                buf.push('[');
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        buf.push(',');
+                    }
+                    item.write_json(buf);
+                }
+                buf.push(']');
+            }
+            Self::Object(members) => {
+                buf.push('{');
+                for (index, (key, value)) in members.iter().enumerate() {
+                    if index > 0 {
+                        buf.push(',');
+                    }
+                    buf.push_str(&json_string(key));
+                    buf.push(':');
+                    value.write_json(buf);
+                }
+                buf.push('}');
+            }
+        }
+    }
+
+    /// Renders the value as a single CSV record (without a line terminator). The fields of the
+    /// record are the member values of an object, the items of an array, or the value itself if it
+    /// is a scalar. [`Value::Null`] and non-finite floats are rendered as empty fields, and nested
+    /// arrays and objects as JSON.
+    pub fn to_csv(&self) -> String {
+        let fields: Vec<&Value> = match self {
+            Self::Array(items) => items.iter().collect(),
+            Self::Object(members) => members.iter().map(|(_, value)| value).collect(),
+            scalar => vec![scalar],
+        };
+        fields
+            .into_iter()
+            .map(|field| csv_field(&field.csv_text()))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Renders the value as the unquoted text of a CSV field.
+    fn csv_text(&self) -> String {
+        match self {
+            Self::Null => String::new(),
+            Self::Float(value) if !value.is_finite() => String::new(),
+            Self::String(value) => value.clone(),
+            Self::Array(_) | Self::Object(_) => self.to_json(),
+            scalar => scalar.to_json(),
+        }
+    }
+
+    /// Renders the value on a single line of text, if it is a scalar or an array of scalars.
+    fn inline_text(&self) -> Option<String> {
+        match self {
+            Self::Null => Some(String::new()),
+            Self::Bool(value) => Some(value.to_string()),
+            Self::Integer(value) => Some(value.to_string()),
+            Self::Float(value) => Some(value.to_string()),
+            Self::String(value) => Some(value.clone()),
+            Self::Array(items) => items
+                .iter()
+                .map(Self::inline_text)
+                .collect::<Option<Vec<_>>>()
+                .map(|items| items.join(", ")),
+            Self::Object(_) => None,
+        }
+    }
+
+    /// Appends the lines of text rendering the value, indented by the given number of spaces.
+    fn text_lines(&self, indent: usize, lines: &mut Vec<String>) {
+        let pad = " ".repeat(indent);
+        if let Some(text) = self.inline_text() {
+            lines.push(format!("{pad}{text}"));
+            return;
+        }
+        let entries: Vec<(Cow<str>, &Value)> = match self {
+            Self::Array(items) => items.iter().map(|item| (Cow::Borrowed("-"), item)).collect(),
+            Self::Object(members) => members
+                .iter()
+                .map(|(key, value)| (Cow::Owned(format!("{key}:")), value))
+                .collect(),
+            _ => unreachable!("scalars are rendered inline"),
+        };
+        for (label, value) in entries {
+            match value.inline_text() {
+                Some(text) if text.is_empty() => lines.push(format!("{pad}{label}")),
+                Some(text) => lines.push(format!("{pad}{label} {text}")),
+                None => {
+                    lines.push(format!("{pad}{label}"));
+                    value.text_lines(indent + 2, lines);
+                }
+            }
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut lines = vec![];
+        self.text_lines(0, &mut lines);
+        f.write_str(&lines.join("\n"))
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Self {
+        Self::Integer(i64::from(value))
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<u8> for Value {
+    fn from(value: u8) -> Self {
+        Self::Integer(i64::from(value))
+    }
+}
+
+impl From<u32> for Value {
+    fn from(value: u32) -> Self {
+        Self::Integer(i64::from(value))
+    }
+}
+
+impl From<f32> for Value {
+    fn from(value: f32) -> Self {
+        Self::Float(f64::from(value))
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Self::String(value.into())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<Cow<'_, str>> for Value {
+    fn from(value: Cow<'_, str>) -> Self {
+        Self::String(value.into_owned())
+    }
+}
+
+impl<V: Into<Value>> From<Vec<V>> for Value {
+    fn from(items: Vec<V>) -> Self {
+        Self::Array(items.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<V: Into<Value>> From<Option<V>> for Value {
+    fn from(value: Option<V>) -> Self {
+        value.map_or(Self::Null, Into::into)
+    }
+}
+
+/// Encodes the given string as a quoted JSON string literal.
+pub(crate) fn json_string(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len() + 2);
+    buf.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            ch if u32::from(ch) < 0x20 => {
+                // writing to a String is infallible
+                let _ = write!(buf, "\\u{:04x}", u32::from(ch));
+            }
+            ch => buf.push(ch),
+        }
+    }
+    buf.push('"');
+    buf
+}
+
+/// Encodes the given string as a CSV field, quoting it only if it contains a delimiter, a quote
+/// or a line break.
+pub(crate) fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.into()
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,109 @@
+// $coverage:ignore-start
+
+use crate::terminal::format::{csv_field, json_string};
+use crate::terminal::{OutputFormat, Value};
+
+#[test]
+fn machine_readable() {
+    assert!(!OutputFormat::Text.is_machine_readable());
+    assert!(OutputFormat::Csv.is_machine_readable());
+    assert!(OutputFormat::Json.is_machine_readable());
+    assert_eq!(OutputFormat::Text, OutputFormat::default());
+}
+
+#[test]
+fn json_string_escapes() {
+    assert_eq!(r#""plain""#, json_string("plain"));
+    assert_eq!(r#""""#, json_string(""));
+    assert_eq!(r#""say \"hi\"\\n""#, json_string("say \"hi\"\\n"));
+    assert_eq!(r#""a\nb\r\tc""#, json_string("a\nb\r\tc"));
+    assert_eq!(r#""\u0007 ü""#, json_string("\x07 ü"));
+}
+
+#[test]
+fn csv_field_quoting() {
+    assert_eq!("plain", csv_field("plain"));
+    assert_eq!("", csv_field(""));
+    assert_eq!("\"a,b\"", csv_field("a,b"));
+    assert_eq!("\"say \"\"hi\"\"\"", csv_field("say \"hi\""));
+    assert_eq!("\"a\nb\"", csv_field("a\nb"));
+}
+
+#[test]
+fn output_format_from_str() {
+    assert_eq!(Ok(OutputFormat::Text), "text".parse());
+    assert_eq!(Ok(OutputFormat::Csv), "CSV".parse());
+    assert_eq!(Ok(OutputFormat::Json), "Json".parse());
+    assert_eq!(
+        Err("unsupported output format 'xml'; expected one of text, csv or json".to_string()),
+        "xml".parse::<OutputFormat>()
+    );
+    for format in [OutputFormat::Text, OutputFormat::Csv, OutputFormat::Json] {
+        assert_eq!(Ok(format), format.to_string().parse());
+    }
+}
+
+fn sample() -> Value {
+    Value::object([
+        ("name", "pump \"A\"".into()),
+        ("running", true.into()),
+        ("rpm", 1200.into()),
+        ("load", 0.75.into()),
+        ("fault", Value::Null),
+        ("tags", vec!["primary", "cooling"].into()),
+        ("readings", Value::Array(vec![
+            Value::object([("at", 1.into()), ("temp", 71.5.into())]),
+            Value::object([("at", 2.into()), ("temp", Option::<f64>::None.into())]),
+        ])),
+        ("limits", Value::object([("max", f64::INFINITY.into())])),
+    ])
+}
+
+#[test]
+fn value_to_json() {
+    assert_eq!(
+        r#"{"name":"pump \"A\"","running":true,"rpm":1200,"load":0.75,"fault":null,"tags":["primary","cooling"],"readings":[{"at":1,"temp":71.5},{"at":2,"temp":null}],"limits":{"max":null}}"#,
+        sample().to_json()
+    );
+    assert_eq!("[]", Value::Array(vec![]).to_json());
+    assert_eq!("{}", Value::Object(vec![]).to_json());
+    assert_eq!("-3", Value::from(-3i64).to_json());
+    assert_eq!("255", Value::from(255u8).to_json());
+    assert_eq!("4000000000", Value::from(4_000_000_000u32).to_json());
+    assert_eq!("1.5", Value::from(1.5f32).to_json());
+}
+
+#[test]
+fn value_to_csv() {
+    assert_eq!(
+        r#""pump ""A""",true,1200,0.75,,"[""primary"",""cooling""]","[{""at"":1,""temp"":71.5},{""at"":2,""temp"":null}]","{""max"":null}""#,
+        sample().to_csv()
+    );
+    assert_eq!("a,3,", Value::from(vec![Value::from("a"), 3.into(), Value::Null]).to_csv());
+    assert_eq!("\"a,b\"", Value::from("a,b").to_csv());
+    assert_eq!("", Value::from(f64::NAN).to_csv());
+    assert_eq!("", Value::Object(vec![]).to_csv());
+}
+
+#[test]
+fn value_to_text() {
+    assert_eq!("\
+    name: pump \"A\"\n\
+    running: true\n\
+    rpm: 1200\n\
+    load: 0.75\n\
+    fault:\n\
+    tags: primary, cooling\n\
+    readings:\n\
+    \x20 -\n\
+    \x20   at: 1\n\
+    \x20   temp: 71.5\n\
+    \x20 -\n\
+    \x20   at: 2\n\
+    \x20   temp:\n\
+    limits:\n\
+    \x20 max: inf", sample().to_string());
+    assert_eq!("plain", Value::from("plain").to_string());
+    assert_eq!("", Value::Null.to_string());
+    assert_eq!("", Value::Object(vec![]).to_string());
+}
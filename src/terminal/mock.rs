@@ -1,12 +1,38 @@
 //! Mocking of a terminal device.
 
-use crate::terminal::{Terminal, AccessTerminalError, streaming};
+mod script;
+mod snapshot;
+
+pub use script::*;
+pub use snapshot::*;
+
+use crate::terminal::{Terminal, AccessTerminalError, OutputFormat, Secret, TerminalSize, streaming};
+use std::thread;
+use std::time::Duration;
 
 /// A single invocation of one of the mock's methods.
+///
+/// The enum is non-exhaustive, as new variants are added when the mock records more of the
+/// [`Terminal`] methods. The `ReadSecret`, `ReadKey`, `Status` and `ClearStatus` variants are a
+/// breaking change from 0.2, for code that matched on the enum exhaustively.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Invocation {
     ReadLine(Result<String, String>),
     Print(String, Result<(), String>),
+
+    /// A [`Terminal::read_secret`] call. The value read is not recorded.
+    ReadSecret(Result<(), String>),
+
+    /// A [`Terminal::read_key`] call, with the bytes of the key press.
+    ReadKey(Result<Vec<u8>, String>),
+
+    /// A [`Terminal::set_status`] call; e.g., an update of a
+    /// [`ProgressBar`](crate::terminal::ProgressBar).
+    Status(String),
+
+    /// A [`Terminal::clear_status`] call.
+    ClearStatus,
 }
 
 impl Invocation {
@@ -14,14 +40,14 @@ impl Invocation {
     pub fn read_line(&self) -> Option<&Result<String, String>> {
         match self {
             Invocation::ReadLine(v) => Some(v),
-            Invocation::Print(_, _) => None
+            Invocation::Print(_, _) | Invocation::ReadSecret(_) | Invocation::ReadKey(_) | Invocation::Status(_) | Invocation::ClearStatus => None
         }
     }
 
     /// Returns a [`Some`] with a reference to the arguments if this is a [`Invocation::Print`] variant, or [`None`] otherwise.
     pub fn print(&self) -> Option<(&str, &Result<(), String>)> {
         match self {
-            Invocation::ReadLine(_) => None,
+            Invocation::ReadLine(_) | Invocation::ReadSecret(_) | Invocation::ReadKey(_) | Invocation::Status(_) | Invocation::ClearStatus => None,
             Invocation::Print(out, res) => Some((out, res))
         }
     }
@@ -54,18 +80,35 @@ impl PrintOutput for Option<(&str, &Result<(), String>)> {
 
 /// Mock [`Terminal`] implementation, containing delegates for the [`Terminal::read_line`] and
 /// [`Terminal::print`] operations, as well as an invocation tracker.
+///
+/// [`Terminal::read_secret`] is served by the `read_line` delegate, but is recorded as an
+/// [`Invocation::ReadSecret`], without the value. Status lines are recorded as
+/// [`Invocation::Status`] and [`Invocation::ClearStatus`], rather than printed. Key presses are
+/// supported only if a [`Mock::on_read_key`] delegate is given.
 pub struct Mock<'d> {
     on_read_line: streaming::InputReader<'d>,
+    on_read_key: Option<streaming::KeyReader<'d>>,
     on_print: streaming::OutputWriter<'d>,
     invocations: Vec<Invocation>,
+    ansi: bool,
+    size: TerminalSize,
+    format: OutputFormat,
+    redraw: bool,
+    interactive: bool,
 }
 
 impl<'d> Default for Mock<'d> {
     fn default() -> Self {
         Self {
             on_read_line: Box::new(|| Ok(String::default())),
+            on_read_key: None,
             on_print: Box::new(|_| Ok(())),
             invocations: vec![],
+            ansi: false,
+            size: TerminalSize::default(),
+            format: OutputFormat::Text,
+            redraw: false,
+            interactive: true,
         }
     }
 }
@@ -81,6 +124,17 @@ impl<'d> Mock<'d> {
         self
     }
 
+    /// Specifies a delegate closure to be invoked on the [`Terminal::read_key`] call, whereupon the
+    /// mock reports support for key presses.
+    #[must_use]
+    pub fn on_read_key(
+        mut self,
+        delegate: impl FnMut() -> Result<Vec<u8>, AccessTerminalError> + 'd,
+    ) -> Self {
+        self.on_read_key = Some(Box::new(delegate));
+        self
+    }
+
     /// Specifies a delegate closure to be invoked on the [`Terminal::print`] call.
     #[must_use]
     pub fn on_print(mut self, delegate: impl FnMut(&str) -> Result<(), AccessTerminalError> + 'd) -> Self {
@@ -88,10 +142,60 @@ impl<'d> Mock<'d> {
         self
     }
 
+    /// Specifies whether the mock reports support for ANSI styling. By default, it does not, so
+    /// that styled output is recorded as plain text.
+    #[must_use]
+    pub fn with_ansi(mut self, ansi: bool) -> Self {
+        self.ansi = ansi;
+        self
+    }
+
+    /// Specifies the dimensions reported by the mock. By default, it reports
+    /// [`TerminalSize::default`].
+    #[must_use]
+    pub fn with_size(mut self, size: TerminalSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Specifies the output format reported by the mock. By default, it reports
+    /// [`OutputFormat::Text`].
+    #[must_use]
+    pub fn with_output_format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Specifies whether the mock reports support for redrawing the status line. By default, it
+    /// does not, so that progress indicators behave as they would on a plain stream.
+    #[must_use]
+    pub fn with_redraw(mut self, redraw: bool) -> Self {
+        self.redraw = redraw;
+        self
+    }
+
+    /// Specifies whether the mock reports its input as typed by a user (see
+    /// [`Terminal::is_interactive`]). By default, it does, as the mock stands in for a user.
+    #[must_use]
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
+    }
+
     /// Lists the invocations that have been recorded against this mock.
     pub fn invocations(&self) -> &[Invocation] {
         &self.invocations[..]
     }
+
+    /// Concatenates everything that has been printed to this mock, in the order of invocation.
+    /// Useful for asserting against a snapshot (see [`assert_snapshot`]).
+    pub fn output(&self) -> String {
+        self.invocations
+            .iter()
+            .filter_map(Invocation::print)
+            .map(|(out, _)| out)
+            .collect()
+    }
 }
 
 impl<'d> Terminal for Mock<'d> {
@@ -99,10 +203,7 @@ impl<'d> Terminal for Mock<'d> {
         let result = (*self.on_print)(s);
         self.invocations.push(Invocation::Print(
             s.into(),
-            result
-                .as_ref()
-                .map(Clone::clone)
-                .map_err(ToString::to_string),
+            result.clone().map_err(|err| err.to_string()),
         ));
         result
     }
@@ -110,22 +211,72 @@ impl<'d> Terminal for Mock<'d> {
     fn read_line(&mut self) -> Result<String, AccessTerminalError> {
         let result = (*self.on_read_line)();
         self.invocations.push(Invocation::ReadLine(
-            result
-                .as_ref()
-                .map(Clone::clone)
-                .map_err(ToString::to_string),
+            result.clone().map_err(|err| err.to_string()),
+        ));
+        result
+    }
+
+    fn read_secret(&mut self) -> Result<Secret, AccessTerminalError> {
+        let result = (*self.on_read_line)();
+        self.invocations.push(Invocation::ReadSecret(
+            result.as_ref().map(|_| ()).map_err(ToString::to_string),
+        ));
+        result.map(Secret::from_line)
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.interactive
+    }
+
+    fn supports_keys(&self) -> bool {
+        self.on_read_key.is_some()
+    }
+
+    fn read_key(&mut self) -> Result<Vec<u8>, AccessTerminalError> {
+        let result = match &mut self.on_read_key {
+            Some(on_read_key) => on_read_key(),
+            None => Err(AccessTerminalError::other("key presses are not supported")),
+        };
+        self.invocations.push(Invocation::ReadKey(
+            result.clone().map_err(|err| err.to_string()),
         ));
         result
     }
+
+    fn supports_ansi(&self) -> bool {
+        self.ansi
+    }
+
+    fn size(&self) -> TerminalSize {
+        self.size
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        self.format
+    }
+
+    fn supports_redraw(&self) -> bool {
+        self.redraw
+    }
+
+    fn set_status(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        self.invocations.push(Invocation::Status(s.into()));
+        Ok(())
+    }
+
+    fn clear_status(&mut self) -> Result<(), AccessTerminalError> {
+        self.invocations.push(Invocation::ClearStatus);
+        Ok(())
+    }
 }
 
 /// Generates a `read_line` closure that returns one item at a time from a pre-canned slice of lines. If the closure
-/// is invoked after the slice is exhausted, it will return an [`AccessTerminalError`].
+/// is invoked after the slice is exhausted, it will return [`AccessTerminalError::end_of_input`].
 pub fn lines<S: ToString + 'static>(lines: &[S]) -> impl FnMut() -> Result<String, AccessTerminalError> + '_ {
     let mut lines = lines;
     move || {
         if lines.is_empty() {
-            return Err(AccessTerminalError("no more lines".into()))
+            return Err(AccessTerminalError::end_of_input())
         }
         let s = &lines[0];
         lines = &lines[1..];
@@ -133,5 +284,56 @@ pub fn lines<S: ToString + 'static>(lines: &[S]) -> impl FnMut() -> Result<Strin
     }
 }
 
+/// Generates a `read_key` closure that returns one key press at a time from a pre-canned slice. If
+/// the closure is invoked after the slice is exhausted, it will return
+/// [`AccessTerminalError::end_of_input`].
+pub fn keys<'k>(keys: &'k [&'k str]) -> impl FnMut() -> Result<Vec<u8>, AccessTerminalError> + 'k {
+    let mut keys = keys.iter();
+    move || match keys.next() {
+        Some(key) => Ok(key.as_bytes().to_vec()),
+        None => Err(AccessTerminalError::end_of_input()),
+    }
+}
+
+/// A simulated input event, for use with [`feed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Feed {
+    /// A complete line of input.
+    Line(String),
+
+    /// The end of the input stream, as if the user pressed Ctrl-D or a piped file was exhausted.
+    EndOfInput,
+
+    /// An interrupt, as if the user pressed Ctrl-C.
+    Interrupt,
+
+    /// A pause before the next event is delivered, simulating slow input.
+    Delay(Duration),
+}
+
+impl Feed {
+    /// Creates a [`Feed::Line`] from anything representable as a [`String`].
+    pub fn line(s: impl ToString) -> Self {
+        Self::Line(s.to_string())
+    }
+}
+
+/// Generates a `read_line` closure that plays back a pre-canned slice of [`Feed`] events. Each invocation
+/// blocks for any leading [`Feed::Delay`]s, then delivers the next event: a line is returned as-is, whereas
+/// [`Feed::EndOfInput`] and [`Feed::Interrupt`] are returned as [`AccessTerminalError::end_of_input`] and
+/// [`AccessTerminalError::interrupted`], respectively. Once the slice is exhausted, the closure behaves as
+/// though [`Feed::EndOfInput`] was reached.
+pub fn feed(events: &[Feed]) -> impl FnMut() -> Result<String, AccessTerminalError> + '_ {
+    let mut events = events.iter();
+    move || loop {
+        match events.next() {
+            Some(Feed::Line(s)) => return Ok(s.clone()),
+            Some(Feed::EndOfInput) | None => return Err(AccessTerminalError::end_of_input()),
+            Some(Feed::Interrupt) => return Err(AccessTerminalError::interrupted()),
+            Some(Feed::Delay(duration)) => thread::sleep(*duration),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests;
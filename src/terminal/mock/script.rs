@@ -0,0 +1,178 @@
+//! Scripted expectations for a [`Mock`] terminal. A [`Script`] is a fluent specification of
+//! the exact sequence of interactions that the code under test is expected to have with the
+//! terminal, e.g., "expect prompt `+>> `, respond `add 1`, expect output containing `2.0`". The
+//! scripted mock panics with a precise message at the first mismatch; [`Script::verify`] asserts
+//! that all expectations were consumed.
+
+use crate::terminal::{AccessTerminalError, Mock};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter};
+use std::rc::Rc;
+
+/// A single scripted interaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expectation {
+    /// A [`Terminal::print`](crate::terminal::Terminal::print) of exactly the given string.
+    Print(String),
+
+    /// A [`Terminal::print`](crate::terminal::Terminal::print) of a string containing the given substring.
+    PrintContaining(String),
+
+    /// A [`Terminal::read_line`](crate::terminal::Terminal::read_line), answered with the given result.
+    ReadLine(Result<String, AccessTerminalError>),
+}
+
+impl Display for Expectation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expectation::Print(s) => write!(f, "print {s:?}"),
+            Expectation::PrintContaining(s) => write!(f, "print containing {s:?}"),
+            Expectation::ReadLine(Ok(s)) => write!(f, "read_line (responding {s:?})"),
+            Expectation::ReadLine(Err(err)) => write!(f, "read_line (failing with '{err}')"),
+        }
+    }
+}
+
+#[derive(Default)]
+struct State {
+    pending: VecDeque<Expectation>,
+    consumed: usize,
+}
+
+impl State {
+    /// Takes the next expectation, panicking if none remain. `actual` describes the offending
+    /// invocation.
+    fn next(&mut self, actual: &str) -> (usize, Expectation) {
+        let Some(expectation) = self.pending.pop_front() else {
+            panic!(
+                "unexpected {actual} after all {} expectation(s) were consumed",
+                self.consumed
+            );
+        };
+        self.consumed += 1;
+        (self.consumed, expectation)
+    }
+
+    fn print(&mut self, s: &str) {
+        let actual = format!("print {s:?}");
+        let (number, expectation) = self.next(&actual);
+        let matched = match &expectation {
+            Expectation::Print(expected) => expected == s,
+            Expectation::PrintContaining(expected) => s.contains(expected.as_str()),
+            Expectation::ReadLine(_) => false,
+        };
+        assert!(matched, "expectation #{number} failed: expected {expectation}, got {actual}");
+    }
+
+    fn read_line(&mut self) -> Result<String, AccessTerminalError> {
+        let (number, expectation) = self.next("read_line");
+        match expectation {
+            Expectation::ReadLine(result) => result,
+            _ => panic!("expectation #{number} failed: expected {expectation}, got read_line"),
+        }
+    }
+}
+
+/// A fluent builder of [`Expectation`]s that produces a scripted [`Mock`].
+///
+/// `Script` is a cheaply cloneable handle: the [`Mock`] created by [`Script::mock`] shares its
+/// state with the script, so that [`Script::verify`] can be called after the mock has been used.
+#[derive(Clone, Default)]
+pub struct Script {
+    state: Rc<RefCell<State>>,
+}
+
+impl Script {
+    /// Appends an arbitrary [`Expectation`].
+    #[must_use]
+    pub fn expect(self, expectation: Expectation) -> Self {
+        self.state.borrow_mut().pending.push_back(expectation);
+        self
+    }
+
+    /// Expects the given prompt to be printed (verbatim, without a trailing newline).
+    #[must_use]
+    pub fn expect_prompt(self, prompt: &str) -> Self {
+        self.expect(Expectation::Print(prompt.into()))
+    }
+
+    /// Expects exactly the given string to be printed.
+    #[must_use]
+    pub fn expect_print(self, s: &str) -> Self {
+        self.expect(Expectation::Print(s.into()))
+    }
+
+    /// Expects the given line to be printed, followed by a newline separator, as per
+    /// [`Terminal::print_line`](crate::terminal::Terminal::print_line).
+    #[must_use]
+    pub fn expect_line(self, line: &str) -> Self {
+        self.expect(Expectation::Print(format!("{line}\n")))
+    }
+
+    /// Expects a string containing the given substring to be printed.
+    #[must_use]
+    pub fn expect_output_containing(self, substring: &str) -> Self {
+        self.expect(Expectation::PrintContaining(substring.into()))
+    }
+
+    /// Expects a line to be read, responding with the given input.
+    #[must_use]
+    pub fn respond(self, input: &str) -> Self {
+        self.expect(Expectation::ReadLine(Ok(input.into())))
+    }
+
+    /// Expects a line to be read, failing with the given error.
+    #[must_use]
+    pub fn respond_err(self, err: AccessTerminalError) -> Self {
+        self.expect(Expectation::ReadLine(Err(err)))
+    }
+
+    /// Expects a line to be read, failing with [`AccessTerminalError::end_of_input`].
+    #[must_use]
+    pub fn respond_end_of_input(self) -> Self {
+        self.respond_err(AccessTerminalError::end_of_input())
+    }
+
+    /// Expects a line to be read, failing with [`AccessTerminalError::interrupted`].
+    #[must_use]
+    pub fn respond_interrupt(self) -> Self {
+        self.respond_err(AccessTerminalError::interrupted())
+    }
+
+    /// Creates a [`Mock`] that plays back this script. The mock panics at the first invocation
+    /// that does not match the next expectation.
+    pub fn mock(&self) -> Mock<'static> {
+        let print_state = Rc::clone(&self.state);
+        let read_line_state = Rc::clone(&self.state);
+        Mock::default()
+            .on_print(move |s| {
+                print_state.borrow_mut().print(s);
+                Ok(())
+            })
+            .on_read_line(move || read_line_state.borrow_mut().read_line())
+    }
+
+    /// The expectations that are yet to be consumed.
+    pub fn pending(&self) -> Vec<Expectation> {
+        self.state.borrow().pending.iter().cloned().collect()
+    }
+
+    /// Asserts that all expectations were consumed.
+    ///
+    /// # Panics
+    /// If one or more expectations remain. The panic message lists the first of them.
+    pub fn verify(&self) {
+        let state = self.state.borrow();
+        if let Some(next) = state.pending.front() {
+            panic!(
+                "{} unconsumed expectation(s), starting with #{}: {next}",
+                state.pending.len(),
+                state.consumed + 1
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
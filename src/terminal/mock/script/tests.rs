@@ -0,0 +1,100 @@
+// $coverage:ignore-start
+
+use std::convert::Infallible;
+use crate::command::{help, quit, Commander};
+use crate::looper::Looper;
+use crate::terminal::{AccessTerminalError, Expectation, Script, Terminal};
+
+#[test]
+fn scripted_looper() {
+    let script = Script::default()
+        .expect_prompt("+>> ")
+        .respond("help")
+        .expect_output_containing("Exits the program")
+        .expect_prompt("+>> ")
+        .respond("quit")
+        .expect_line("Exiting.");
+    let mut term = script.mock();
+    let commander = Commander::<_, Infallible, _>::new(vec![
+        Box::new(help::Parser::default()),
+        Box::new(quit::Parser::default()),
+    ]);
+    let mut context = ();
+    Looper::new(&mut term, &commander, &mut context).run().unwrap();
+    script.verify();
+    assert!(script.pending().is_empty());
+}
+
+#[test]
+fn respond_err() {
+    let script = Script::default()
+        .respond_err(AccessTerminalError::other("broken pipe"))
+        .respond_end_of_input()
+        .respond_interrupt();
+    let mut term = script.mock();
+    assert_eq!(AccessTerminalError::other("broken pipe"), term.read_line().unwrap_err());
+    assert_eq!(AccessTerminalError::end_of_input(), term.read_line().unwrap_err());
+    assert_eq!(AccessTerminalError::interrupted(), term.read_line().unwrap_err());
+    script.verify();
+}
+
+#[test]
+#[should_panic(expected = "expectation #2 failed: expected print \"+>> \", got print \"->> \"")]
+fn print_mismatch() {
+    let script = Script::default().expect_print("hello").expect_prompt("+>> ");
+    let mut term = script.mock();
+    term.print("hello").unwrap();
+    term.print("->> ").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "expectation #1 failed: expected print containing \"2.0\", got print \"1.0\\n\"")]
+fn print_containing_mismatch() {
+    let script = Script::default().expect_output_containing("2.0");
+    let mut term = script.mock();
+    term.print_line("1.0").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "expectation #1 failed: expected read_line (responding \"add 1\"), got print \"hello\"")]
+fn print_instead_of_read_line() {
+    let script = Script::default().respond("add 1");
+    let mut term = script.mock();
+    term.print("hello").unwrap();
+}
+
+#[test]
+#[should_panic(expected = "expectation #1 failed: expected print \"hello\", got read_line")]
+fn read_line_instead_of_print() {
+    let script = Script::default().expect_print("hello");
+    let mut term = script.mock();
+    term.read_line().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "unexpected read_line after all 1 expectation(s) were consumed")]
+fn exhausted() {
+    let script = Script::default().expect_print("hello");
+    let mut term = script.mock();
+    term.print("hello").unwrap();
+    term.read_line().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "2 unconsumed expectation(s), starting with #2: read_line (responding \"quit\")")]
+fn verify_unconsumed() {
+    let script = Script::default()
+        .expect_prompt("+>> ")
+        .respond("quit")
+        .expect_line("Exiting.");
+    let mut term = script.mock();
+    term.print("+>> ").unwrap();
+    assert_eq!(
+        vec![
+            Expectation::ReadLine(Ok("quit".into())),
+            Expectation::Print("Exiting.\n".into())
+        ],
+        script.pending()
+    );
+    script.verify();
+}
@@ -0,0 +1,152 @@
+//! Snapshot assertions for terminal output. The expected output is stored in a file alongside the
+//! test; the assertion compares the actual output against the file's contents after normalising
+//! both. Setting the [`UPDATE_SNAPSHOTS_VAR`] environment variable (to anything other than `0`)
+//! causes the snapshot files to be (re)written from the actual output instead.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// The environment variable that enables the [`SnapshotMode::Update`] mode.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "REVOLVER_UPDATE_SNAPSHOTS";
+
+/// Determines how a snapshot file is treated when asserting against it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapshotMode {
+    /// The actual output is compared against the stored snapshot, which must exist.
+    Verify,
+
+    /// The stored snapshot is (over)written with the actual output. The comparison always passes.
+    Update,
+}
+
+impl SnapshotMode {
+    /// Obtains the mode from the [`UPDATE_SNAPSHOTS_VAR`] environment variable, defaulting to
+    /// [`SnapshotMode::Verify`] if the variable is unset, empty or `0`.
+    pub fn from_env() -> Self {
+        match std::env::var(UPDATE_SNAPSHOTS_VAR) {
+            Ok(val) if !val.is_empty() && val != "0" => Self::Update,
+            _ => Self::Verify,
+        }
+    }
+}
+
+/// Produced when the actual output does not match the stored snapshot, or when the snapshot
+/// could not be accessed.
+#[derive(Debug, Error)]
+pub enum SnapshotError {
+    #[error("missing snapshot '{}' (set {UPDATE_SNAPSHOTS_VAR}=1 to create it)", path.display())]
+    Missing { path: PathBuf },
+
+    #[error("snapshot '{}' does not match (set {UPDATE_SNAPSHOTS_VAR}=1 to update it)\n--- expected\n{expected}\n--- actual\n{actual}", path.display())]
+    Mismatch {
+        path: PathBuf,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("io: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Removes ANSI escape sequences (colours, text attributes, cursor movement, etc.) from the given
+/// string slice.
+pub fn strip_ansi(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            buf.push(ch);
+            continue;
+        }
+
+        match chars.next() {
+            Some('[') => {
+                // CSI: parameter and intermediate bytes, terminated by a final byte in '@'..='~'
+                for ch in chars.by_ref() {
+                    if ('@'..='~').contains(&ch) {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                // OSC: terminated by BEL or ST (ESC '\')
+                while let Some(ch) = chars.next() {
+                    if ch == '\x07' {
+                        break;
+                    }
+                    if ch == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    buf
+}
+
+/// Normalises terminal output for comparison: ANSI escape sequences are removed, line
+/// endings are converted to `\n` and trailing whitespace is trimmed from each line.
+pub fn normalise(s: &str) -> String {
+    strip_ansi(s)
+        .replace("\r\n", "\n")
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Compares `actual` against the snapshot stored at `path`, both normalised with [`normalise`].
+/// In [`SnapshotMode::Update`], the snapshot file (and any missing parent directories) is written
+/// instead.
+///
+/// # Errors
+/// [`SnapshotError`] if the snapshot is missing, does not match, or could not be accessed.
+pub fn check_snapshot(path: impl AsRef<Path>, actual: &str, mode: SnapshotMode) -> Result<(), SnapshotError> {
+    let path = path.as_ref();
+    let actual = normalise(actual);
+    match mode {
+        SnapshotMode::Update => {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(path, format!("{actual}\n"))?;
+            Ok(())
+        }
+        SnapshotMode::Verify => {
+            let expected = match fs::read_to_string(path) {
+                Ok(expected) => normalise(&expected),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    return Err(SnapshotError::Missing { path: path.into() })
+                }
+                Err(err) => return Err(err.into()),
+            };
+            if expected == actual {
+                Ok(())
+            } else {
+                Err(SnapshotError::Mismatch {
+                    path: path.into(),
+                    expected,
+                    actual,
+                })
+            }
+        }
+    }
+}
+
+/// Asserts that `actual` matches the snapshot stored at `path`, using the mode obtained from
+/// [`SnapshotMode::from_env`].
+///
+/// # Panics
+/// If the snapshot is missing, does not match, or could not be accessed.
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str) {
+    if let Err(err) = check_snapshot(path, actual, SnapshotMode::from_env()) {
+        panic!("{err}");
+    }
+}
+
+#[cfg(test)]
+mod tests;
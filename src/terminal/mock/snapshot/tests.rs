@@ -0,0 +1,52 @@
+// $coverage:ignore-start
+
+use std::fs;
+use flanker_temp::TempPath;
+use crate::terminal::{check_snapshot, normalise, strip_ansi, SnapshotError, SnapshotMode};
+
+#[test]
+fn strip_ansi_removes_escape_sequences() {
+    assert_eq!("plain", strip_ansi("plain"));
+    assert_eq!("bold green", strip_ansi("\x1b[1mbold\x1b[0m \x1b[92mgreen\x1b[0m"));
+    assert_eq!("cleared", strip_ansi("\x1b[2Kcleared"));
+    assert_eq!("title", strip_ansi("\x1b]0;window\x07title"));
+    assert_eq!("link", strip_ansi("\x1b]8;;http://example.com\x1b\\link"));
+}
+
+#[test]
+fn normalise_line_endings_and_trailing_whitespace() {
+    assert_eq!("one\ntwo\n\nthree", normalise("one  \r\ntwo\x1b[0m \n\nthree\n"));
+}
+
+#[test]
+fn verify_missing() {
+    let temp = TempPath::with_extension("snap");
+    let err = check_snapshot(&temp, "hello", SnapshotMode::Verify).unwrap_err();
+    assert!(matches!(err, SnapshotError::Missing { .. }), "{err:?}");
+    assert!(err.to_string().contains("REVOLVER_UPDATE_SNAPSHOTS=1"));
+}
+
+#[test]
+fn update_then_verify() {
+    let temp = TempPath::with_extension("snap");
+    check_snapshot(&temp, "\x1b[1mhello\x1b[0m\n", SnapshotMode::Update).unwrap();
+    assert_eq!("hello\n", fs::read_to_string(&temp).unwrap());
+
+    check_snapshot(&temp, "hello", SnapshotMode::Verify).unwrap();
+    check_snapshot(&temp, "\x1b[33mhello\x1b[0m  \r\n", SnapshotMode::Verify).unwrap();
+}
+
+#[test]
+fn verify_mismatch() {
+    let temp = TempPath::with_extension("snap");
+    fs::write(&temp, "hello\n").unwrap();
+    let err = check_snapshot(&temp, "goodbye", SnapshotMode::Verify).unwrap_err();
+    match &err {
+        SnapshotError::Mismatch { expected, actual, .. } => {
+            assert_eq!("hello", expected);
+            assert_eq!("goodbye", actual);
+        }
+        _ => panic!("unexpected {err:?}"),
+    }
+    assert!(err.to_string().contains("--- expected\nhello\n--- actual\ngoodbye"));
+}
@@ -1,7 +1,8 @@
 // $coverage:ignore-start
 
 use std::cell::RefCell;
-use crate::terminal::{Invocation, Mock, mock, Terminal, AccessTerminalError, ReadLineInput, PrintOutput};
+use std::time::{Duration, Instant};
+use crate::terminal::{Invocation, Mock, mock, Terminal, AccessTerminalError, ReadLineInput, PrintOutput, Feed, TerminalSize};
 
 #[test]
 fn invocation_variants() {
@@ -14,6 +15,31 @@ fn invocation_variants() {
     assert!(inv.read_line().is_none());
     assert!(inv.print().is_some());
     assert_eq!("foo", inv.print().unwrap_output());
+
+    for inv in [Invocation::ReadSecret(Ok(())), Invocation::Status("busy".into()), Invocation::ClearStatus] {
+        assert!(inv.read_line().is_none());
+        assert!(inv.print().is_none());
+    }
+}
+
+#[test]
+fn read_secret_not_recorded() {
+    let events = [Feed::line("hunter2\n"), Feed::Interrupt];
+    let mut mock = Mock::default().on_read_line(mock::feed(&events));
+
+    assert_eq!("hunter2", mock.read_secret().unwrap().expose());
+    assert_eq!(AccessTerminalError::interrupted(), mock.read_secret().unwrap_err());
+
+    assert_eq!(vec![
+        Invocation::ReadSecret(Ok(())),
+        Invocation::ReadSecret(Err(AccessTerminalError::interrupted().to_string())),
+    ], mock.invocations());
+}
+
+#[test]
+fn size() {
+    assert_eq!(TerminalSize::new(80, 24), Mock::default().size());
+    assert_eq!(TerminalSize::new(40, 10), Mock::default().with_size(TerminalSize::new(40, 10)).size());
 }
 
 #[test]
@@ -68,13 +94,13 @@ fn custom_delegates() {
 fn custom_delegates_with_error() {
     let mut mock = Mock::default()
         .on_print(|_| {
-            Err(AccessTerminalError("broken pipe".into()))
+            Err(AccessTerminalError::other("broken pipe"))
         })
         .on_read_line(|| {
-            Err(AccessTerminalError("already exists".into()))
+            Err(AccessTerminalError::other("already exists"))
         });
-    assert_eq!(AccessTerminalError("broken pipe".into()), mock.print("hello").unwrap_err());
-    assert_eq!(AccessTerminalError("already exists".into()), mock.read_line().unwrap_err());
+    assert_eq!(AccessTerminalError::other("broken pipe"), mock.print("hello").unwrap_err());
+    assert_eq!(AccessTerminalError::other("already exists"), mock.read_line().unwrap_err());
 
     assert_eq!(vec![
         Invocation::Print("hello".into(), Err("broken pipe".into())),
@@ -90,7 +116,38 @@ fn lines() {
     assert_eq!("one", mock.read_line().unwrap());
     assert_eq!("two", mock.read_line().unwrap());
     assert_eq!("three", mock.read_line().unwrap());
-    assert_eq!(AccessTerminalError("no more lines".into()), mock.read_line().err().unwrap());
+    assert_eq!(AccessTerminalError::end_of_input(), mock.read_line().err().unwrap());
+}
+
+#[test]
+fn feed() {
+    let events = [
+        Feed::line("one"),
+        Feed::Interrupt,
+        Feed::Delay(Duration::from_millis(10)),
+        Feed::line("two"),
+        Feed::EndOfInput,
+        Feed::line("three"),
+    ];
+    let mut mock = Mock::default().on_read_line(mock::feed(&events));
+
+    assert_eq!("one", mock.read_line().unwrap());
+    assert_eq!(AccessTerminalError::interrupted(), mock.read_line().unwrap_err());
+    let start = Instant::now();
+    assert_eq!("two", mock.read_line().unwrap());
+    assert!(start.elapsed() >= Duration::from_millis(10));
+    assert_eq!(AccessTerminalError::end_of_input(), mock.read_line().unwrap_err());
+    assert_eq!("three", mock.read_line().unwrap());
+    assert_eq!(AccessTerminalError::end_of_input(), mock.read_line().unwrap_err());
+
+    assert_eq!(vec![
+        Invocation::ReadLine(Ok("one".into())),
+        Invocation::ReadLine(Err("interrupted".into())),
+        Invocation::ReadLine(Ok("two".into())),
+        Invocation::ReadLine(Err("end of input".into())),
+        Invocation::ReadLine(Ok("three".into())),
+        Invocation::ReadLine(Err("end of input".into())),
+    ], mock.invocations());
 }
 
 #[test]
@@ -98,4 +155,14 @@ fn invocation_implements_debug() {
     let inv = Invocation::Print("test".into(), Ok(()));
     let s = format!("{inv:?}");
     assert!(s.contains("test"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn output() {
+    let mut mock = Mock::default()
+        .on_read_line(mock::lines(&["ignored"]));
+    mock.print("one ").unwrap();
+    mock.read_line().unwrap();
+    mock.print_line("two").unwrap();
+    assert_eq!("one two\n", mock.output());
+}
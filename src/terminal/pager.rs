@@ -0,0 +1,284 @@
+//! Paging of long output, with `less`-like navigation. The [`Pager`] itself is a navigation model
+//! over the lines of a text; it is driven by key presses on an interactive terminal (see
+//! [`OutputAdapter::with_pager`](crate::terminal::OutputAdapter::with_pager)). The [`Paged`]
+//! wrapper buffers the output of a command so that it can be paged as a whole.
+
+use crate::terminal::{
+    AccessTerminalError, AccessTerminalErrorKind, OutputFormat, Secret, Style, Terminal, TerminalSize,
+};
+
+/// A key press recognised by the [`Pager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PagerKey {
+    /// Scroll down by one line: `j`, Enter or the down arrow.
+    LineDown,
+
+    /// Scroll up by one line: `k` or the up arrow.
+    LineUp,
+
+    /// Scroll down by one page: Space, `f` or Page Down.
+    PageDown,
+
+    /// Scroll up by one page: `b` or Page Up.
+    PageUp,
+
+    /// Go to the first line: `g`, `<` or Home.
+    Top,
+
+    /// Go to the last page: `G`, `>` or End.
+    Bottom,
+
+    /// Exit the pager: `q` or Ctrl-C.
+    Quit,
+
+    /// Any other key, which is ignored.
+    Other,
+}
+
+impl PagerKey {
+    /// Decodes a key press from the bytes read from a terminal in raw mode.
+    pub fn decode(bytes: &[u8]) -> Self {
+        match bytes {
+            b"j" | b"\r" | b"\n" | b"\x1b[B" | b"\x1bOB" => Self::LineDown,
+            b"k" | b"\x1b[A" | b"\x1bOA" => Self::LineUp,
+            b" " | b"f" | b"\x1b[6~" => Self::PageDown,
+            b"b" | b"\x1b[5~" => Self::PageUp,
+            b"g" | b"<" | b"\x1b[H" | b"\x1b[1~" => Self::Top,
+            b"G" | b">" | b"\x1b[F" | b"\x1b[4~" => Self::Bottom,
+            b"q" | b"Q" | b"\x03" => Self::Quit,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Navigation over the lines of a text, through a viewport of a fixed number of rows. Lines that
+/// are wider than the terminal are wrapped, with each display row scrolled separately.
+#[derive(Debug, Clone)]
+pub struct Pager<'t> {
+    lines: Vec<&'t str>,
+    columns: usize,
+    rows: usize,
+    top: usize,
+}
+
+impl<'t> Pager<'t> {
+    /// Creates a pager over the given text, for a terminal of the given size, initially showing
+    /// the first page. The viewport spans the height of the terminal, less the status line.
+    pub fn new(text: &'t str, size: TerminalSize) -> Self {
+        let columns = size.columns.max(1);
+        Self {
+            lines: text.lines().flat_map(|line| display_rows(line, columns)).collect(),
+            columns,
+            rows: size.rows.saturating_sub(1).max(1),
+            top: 0,
+        }
+    }
+
+    /// Would the text overflow a terminal of the given size, allowing for a trailing prompt? Lines
+    /// that are wider than the terminal count for as many rows as they wrap onto.
+    pub fn is_needed(text: &str, size: TerminalSize) -> bool {
+        let columns = size.columns.max(1);
+        text.lines().map(|line| display_rows(line, columns).len()).sum::<usize>() >= size.rows
+    }
+
+    /// Applies the given key press, returning `false` if the pager should exit.
+    pub fn handle(&mut self, key: PagerKey) -> bool {
+        let last_top = self.lines.len().saturating_sub(self.rows);
+        self.top = match key {
+            PagerKey::LineDown => self.top + 1,
+            PagerKey::LineUp => self.top.saturating_sub(1),
+            PagerKey::PageDown => self.top + self.rows,
+            PagerKey::PageUp => self.top.saturating_sub(self.rows),
+            PagerKey::Top => 0,
+            PagerKey::Bottom => last_top,
+            PagerKey::Quit => return false,
+            PagerKey::Other => self.top,
+        }
+        .min(last_top);
+        true
+    }
+
+    /// The display rows in the viewport.
+    pub fn visible(&self) -> &[&'t str] {
+        let end = (self.top + self.rows).min(self.lines.len());
+        &self.lines[self.top..end]
+    }
+
+    /// Is the last display row in the viewport?
+    pub fn at_end(&self) -> bool {
+        self.top + self.rows >= self.lines.len()
+    }
+
+    /// The status line, shown below the viewport.
+    pub fn status(&self) -> String {
+        if self.at_end() {
+            "(END) q: quit".into()
+        } else {
+            format!(
+                "lines {}-{} of {} (space: next page, b: previous page, q: quit)",
+                self.top + 1,
+                self.top + self.rows,
+                self.lines.len()
+            )
+        }
+    }
+
+    /// Renders the screen: clears it, then prints the visible rows and the status line, truncated
+    /// to the width of the terminal. The status line is shown in reverse video if `ansi` is set.
+    pub fn render(&self, ansi: bool) -> String {
+        let mut screen = String::from("\x1b[H\x1b[2J");
+        for line in self.visible() {
+            screen.push_str(line);
+            screen.push('\n');
+        }
+        let status = self.status().chars().take(self.columns).collect::<String>();
+        if ansi {
+            screen.push_str(&Style::default().reverse().paint(&status));
+        } else {
+            screen.push_str(&status);
+        }
+        screen
+    }
+}
+
+/// Splits a line into the rows it occupies on a terminal of the given width. ANSI escape sequences
+/// take up no width.
+fn display_rows(line: &str, columns: usize) -> Vec<&str> {
+    let mut rows = vec![];
+    let mut start = 0;
+    let mut width = 0;
+    let mut chars = line.char_indices().peekable();
+    while let Some((index, ch)) = chars.next() {
+        if ch == '\x1b' {
+            if chars.next_if(|&(_, ch)| ch == '[').is_some() {
+                while chars.next_if(|&(_, ch)| !('\x40'..='\x7e').contains(&ch)).is_some() {}
+                chars.next();
+            }
+            continue;
+        }
+        if width == columns {
+            rows.push(&line[start..index]);
+            start = index;
+            width = 0;
+        }
+        width += 1;
+    }
+    rows.push(&line[start..]);
+    rows
+}
+
+/// Runs the pager over the given text on the given terminal, using an alternate screen buffer, until
+/// the user quits or the input is exhausted. Key presses are read with [`Terminal::read_key`].
+pub(crate) fn run<T: Terminal + ?Sized>(terminal: &mut T, text: &str) -> Result<(), AccessTerminalError> {
+    let mut pager = Pager::new(text, terminal.size());
+    let ansi = terminal.supports_ansi();
+    terminal.print("\x1b[?1049h")?;
+    let result = (|| loop {
+        terminal.print(&pager.render(ansi))?;
+        let key = match terminal.read_key() {
+            Ok(key) => key,
+            Err(err) if err.kind() == AccessTerminalErrorKind::EndOfInput => return Ok(()),
+            Err(err) => return Err(err),
+        };
+        if !pager.handle(PagerKey::decode(&key)) {
+            return Ok(());
+        }
+    })();
+    terminal.print("\x1b[?1049l")?;
+    result
+}
+
+/// A [`Terminal`] that buffers everything printed to it, and pages the buffered output on the
+/// underlying terminal when [`Paged::finish`] is called. Any read flushes the buffer first, so
+/// that prompts are displayed. If the wrapper is dropped without being finished (e.g., when a
+/// command returns early with an error), the buffered output is paged then, but any error in
+/// doing so is ignored.
+///
+/// For example, a command that prints a long listing line by line may wrap its terminal in a
+/// [`Paged`] for the duration of the listing.
+pub struct Paged<'t, T: Terminal + ?Sized> {
+    terminal: &'t mut T,
+    buf: String,
+}
+
+impl<'t, T: Terminal + ?Sized> Paged<'t, T> {
+    /// Creates a [`Paged`] wrapper over the given terminal.
+    pub fn new(terminal: &'t mut T) -> Self {
+        Self {
+            terminal,
+            buf: String::default(),
+        }
+    }
+
+    /// Pages the buffered output on the underlying terminal (see [`Terminal::page`]).
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    pub fn finish(mut self) -> Result<(), AccessTerminalError> {
+        self.flush()
+    }
+
+    fn flush(&mut self) -> Result<(), AccessTerminalError> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let buf = std::mem::take(&mut self.buf);
+        self.terminal.page(&buf)
+    }
+}
+
+impl<T: Terminal + ?Sized> Drop for Paged<'_, T> {
+    /// Pages the buffered output if the wrapper was abandoned without being finished.
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+impl<T: Terminal + ?Sized> Terminal for Paged<'_, T> {
+    fn print(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        self.buf.push_str(s);
+        Ok(())
+    }
+
+    fn page(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        self.print(s)
+    }
+
+    fn read_line(&mut self) -> Result<String, AccessTerminalError> {
+        self.flush()?;
+        self.terminal.read_line()
+    }
+
+    fn read_secret(&mut self) -> Result<Secret, AccessTerminalError> {
+        self.flush()?;
+        self.terminal.read_secret()
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.terminal.is_interactive()
+    }
+
+    fn supports_keys(&self) -> bool {
+        self.terminal.supports_keys()
+    }
+
+    fn read_key(&mut self) -> Result<Vec<u8>, AccessTerminalError> {
+        self.flush()?;
+        self.terminal.read_key()
+    }
+
+    fn supports_ansi(&self) -> bool {
+        self.terminal.supports_ansi()
+    }
+
+    fn size(&self) -> TerminalSize {
+        self.terminal.size()
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        self.terminal.output_format()
+    }
+}
+
+#[cfg(test)]
+mod tests;
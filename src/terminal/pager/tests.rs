@@ -0,0 +1,163 @@
+// $coverage:ignore-start
+
+use crate::terminal::{keys, AccessTerminalError, Invocation, Mock, Paged, Pager, PagerKey, Terminal, TerminalSize};
+
+fn numbered(count: usize) -> String {
+    (1..=count).map(|i| format!("line {i}\n")).collect()
+}
+
+#[test]
+fn decode_keys() {
+    assert_eq!(PagerKey::LineDown, PagerKey::decode(b"j"));
+    assert_eq!(PagerKey::LineDown, PagerKey::decode(b"\r"));
+    assert_eq!(PagerKey::LineDown, PagerKey::decode(b"\x1b[B"));
+    assert_eq!(PagerKey::LineUp, PagerKey::decode(b"\x1b[A"));
+    assert_eq!(PagerKey::PageDown, PagerKey::decode(b" "));
+    assert_eq!(PagerKey::PageDown, PagerKey::decode(b"\x1b[6~"));
+    assert_eq!(PagerKey::PageUp, PagerKey::decode(b"b"));
+    assert_eq!(PagerKey::Top, PagerKey::decode(b"g"));
+    assert_eq!(PagerKey::Bottom, PagerKey::decode(b"G"));
+    assert_eq!(PagerKey::Quit, PagerKey::decode(b"q"));
+    assert_eq!(PagerKey::Quit, PagerKey::decode(b"\x03"));
+    assert_eq!(PagerKey::Other, PagerKey::decode(b"x"));
+    assert_eq!(PagerKey::Other, PagerKey::decode(b"jj"));
+}
+
+#[test]
+fn navigate() {
+    let text = numbered(10);
+    let mut pager = Pager::new(&text, TerminalSize::new(80, 5));
+    assert_eq!(&["line 1", "line 2", "line 3", "line 4"], pager.visible());
+    assert!(!pager.at_end());
+    assert_eq!("lines 1-4 of 10 (space: next page, b: previous page, q: quit)", pager.status());
+
+    assert!(pager.handle(PagerKey::LineDown));
+    assert_eq!("line 2", pager.visible()[0]);
+    assert!(pager.handle(PagerKey::PageDown));
+    assert_eq!("line 6", pager.visible()[0]);
+    assert!(pager.handle(PagerKey::PageDown));
+    assert_eq!(&["line 7", "line 8", "line 9", "line 10"], pager.visible());
+    assert!(pager.at_end());
+    assert_eq!("(END) q: quit", pager.status());
+    assert!(pager.handle(PagerKey::LineDown));
+    assert_eq!("line 7", pager.visible()[0]);
+
+    assert!(pager.handle(PagerKey::PageUp));
+    assert_eq!("line 3", pager.visible()[0]);
+    assert!(pager.handle(PagerKey::LineUp));
+    assert_eq!("line 2", pager.visible()[0]);
+    assert!(pager.handle(PagerKey::Other));
+    assert_eq!("line 2", pager.visible()[0]);
+    assert!(pager.handle(PagerKey::Bottom));
+    assert_eq!("line 7", pager.visible()[0]);
+    assert!(pager.handle(PagerKey::Top));
+    assert_eq!("line 1", pager.visible()[0]);
+    assert!(pager.handle(PagerKey::LineUp));
+    assert_eq!("line 1", pager.visible()[0]);
+
+    assert!(!pager.handle(PagerKey::Quit));
+}
+
+#[test]
+fn short_text() {
+    let text = numbered(2);
+    let mut pager = Pager::new(&text, TerminalSize::new(80, 5));
+    assert!(pager.at_end());
+    assert!(pager.handle(PagerKey::PageDown));
+    assert_eq!(&["line 1", "line 2"], pager.visible());
+
+    assert!(!Pager::is_needed(&text, TerminalSize::new(80, 3)));
+    assert!(Pager::is_needed(&text, TerminalSize::new(80, 2)));
+}
+
+#[test]
+fn wrapped_lines() {
+    let text = "abcdefghij\n\x1b[1mbold\x1b[0m\n\nxyz\n";
+    assert!(!Pager::is_needed(text, TerminalSize::new(10, 5)));
+    assert!(Pager::is_needed(text, TerminalSize::new(4, 6)));
+
+    let mut pager = Pager::new(text, TerminalSize::new(4, 4));
+    assert_eq!(&["abcd", "efgh", "ij"], pager.visible());
+    assert!(pager.handle(PagerKey::Bottom));
+    assert_eq!(&["\x1b[1mbold\x1b[0m", "", "xyz"], pager.visible());
+    assert_eq!("(END", pager.render(false).lines().last().unwrap());
+}
+
+#[test]
+fn render() {
+    let text = numbered(3);
+    let pager = Pager::new(&text, TerminalSize::new(80, 3));
+    assert_eq!(
+        "\x1b[H\x1b[2Jline 1\nline 2\n\x1b[7mlines 1-2 of 3 (space: next page, b: previous page, q: quit)\x1b[0m",
+        pager.render(true)
+    );
+    assert_eq!(
+        "\x1b[H\x1b[2Jline 1\nline 2\nlines 1-2 of 3 (space: next page, b: previous page, q: quit)",
+        pager.render(false)
+    );
+}
+
+#[test]
+fn run_reads_keys() {
+    let text = numbered(4);
+    let mut mock = Mock::default()
+        .with_size(TerminalSize::new(20, 3))
+        .on_read_key(keys(&["\x1b[B", "x", "q"]));
+    super::run(&mut mock, &text).unwrap();
+
+    let screens = mock.invocations().iter().filter_map(Invocation::print).map(|(out, _)| out).collect::<Vec<_>>();
+    assert_eq!(&[
+        "\x1b[?1049h",
+        "\x1b[H\x1b[2Jline 1\nline 2\nlines 1-2 of 4 (spac",
+        "\x1b[H\x1b[2Jline 2\nline 3\nlines 2-3 of 4 (spac",
+        "\x1b[H\x1b[2Jline 2\nline 3\nlines 2-3 of 4 (spac",
+        "\x1b[?1049l",
+    ], &screens[..]);
+
+    let mut mock = Mock::default().with_size(TerminalSize::new(20, 3)).on_read_key(keys(&[]));
+    super::run(&mut mock, &text).unwrap();
+    assert_eq!(Invocation::Print("\x1b[?1049l".into(), Ok(())), *mock.invocations().last().unwrap());
+}
+
+#[test]
+fn paged_buffers_until_finish() {
+    let mut mock = Mock::default().with_ansi(true);
+    let mut paged = Paged::new(&mut mock);
+    paged.print_line("one").unwrap();
+    paged.print_line("two").unwrap();
+    assert!(paged.supports_ansi());
+    paged.finish().unwrap();
+
+    assert_eq!(&[Invocation::Print("one\ntwo\n".into(), Ok(()))], mock.invocations());
+}
+
+#[test]
+fn paged_flushes_on_drop() {
+    let mut mock = Mock::default();
+    let result: Result<(), AccessTerminalError> = (|| {
+        let mut paged = Paged::new(&mut mock);
+        paged.print_line("partial")?;
+        Err(AccessTerminalError::other("failed"))
+    })();
+    assert!(result.is_err());
+
+    assert_eq!(&[Invocation::Print("partial\n".into(), Ok(()))], mock.invocations());
+}
+
+#[test]
+fn paged_flushes_before_read() {
+    let mut mock = Mock::default();
+    let mut paged = Paged::new(&mut mock);
+    paged.print("Password: ").unwrap();
+    paged.read_secret().unwrap();
+    paged.print("Name: ").unwrap();
+    paged.read_line().unwrap();
+    paged.finish().unwrap();
+
+    assert_eq!(&[
+        Invocation::Print("Password: ".into(), Ok(())),
+        Invocation::ReadSecret(Ok(())),
+        Invocation::Print("Name: ".into(), Ok(())),
+        Invocation::ReadLine(Ok("".into())),
+    ], mock.invocations());
+}
@@ -0,0 +1,286 @@
+//! Progress indicators for long-running commands: a determinate [`ProgressBar`] and an
+//! indeterminate [`Spinner`]. Both are drawn on the status line of the terminal (see
+//! [`Terminal::set_status`]), which is redrawn in place on a terminal that
+//! [supports redrawing](Terminal::supports_redraw). On other terminals, progress is reported with
+//! periodic lines instead, so that logs are not flooded. Nothing is drawn in a machine-readable
+//! output format.
+
+use crate::terminal::{AccessTerminalError, Terminal};
+use std::time::{Duration, Instant};
+
+/// The default minimum interval between redraws of a progress indicator, to limit the cost of
+/// frequent updates.
+pub const DEFAULT_REFRESH: Duration = Duration::from_millis(100);
+
+/// The default interval between the lines reporting the progress of a [`Spinner`] on a terminal
+/// that does not support redrawing.
+pub const DEFAULT_LOG_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The percentage step between the lines reporting the progress of a [`ProgressBar`] on a
+/// terminal that does not support redrawing.
+const LOG_STEP_PERCENT: u64 = 10;
+
+/// The maximum width of the bar, excluding the brackets.
+const MAX_BAR_WIDTH: usize = 30;
+
+/// The animation frames of a [`Spinner`].
+const SPINNER_FRAMES: [char; 4] = ['|', '/', '-', '\\'];
+
+/// A determinate progress indicator, showing the completed fraction of a known amount of work;
+/// e.g., `Copying [#########---------]  50% (500/1000)`.
+///
+/// On a terminal that supports redrawing, the bar is redrawn in place as the position advances,
+/// at most once per refresh interval. Otherwise, a line is printed on the first update and
+/// whenever another 10% of the work is completed. On [`ProgressBar::finish`], the final state of the bar
+/// is printed as an ordinary line.
+pub struct ProgressBar<'t, T: Terminal + ?Sized> {
+    terminal: &'t mut T,
+    label: String,
+    total: u64,
+    position: u64,
+    refresh: Duration,
+    last_draw: Option<Instant>,
+    last_logged_step: Option<u64>,
+    last_logged: Option<String>,
+    finished: bool,
+}
+
+impl<'t, T: Terminal + ?Sized> ProgressBar<'t, T> {
+    /// Creates a progress bar for the given amount of work, initially at position 0. Nothing is
+    /// drawn until the first update (see [`ProgressBar::set`]).
+    pub fn new(terminal: &'t mut T, label: impl Into<String>, total: u64) -> Self {
+        Self {
+            terminal,
+            label: label.into(),
+            total,
+            position: 0,
+            refresh: DEFAULT_REFRESH,
+            last_draw: None,
+            last_logged_step: None,
+            last_logged: None,
+            finished: false,
+        }
+    }
+
+    /// Specifies the minimum interval between redraws. By default, it is [`DEFAULT_REFRESH`].
+    #[must_use]
+    pub fn with_refresh(mut self, refresh: Duration) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// The current position.
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// The total amount of work.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// The completed percentage, between 0 and 100. An empty amount of work is 100% complete.
+    pub fn percent(&self) -> u64 {
+        // widened, so that the multiplication cannot overflow
+        (u128::from(self.position.min(self.total)) * 100)
+            .checked_div(u128::from(self.total))
+            .map_or(100, |percent| u64::try_from(percent).unwrap_or(100))
+    }
+
+    /// Sets the position, drawing the bar if due.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    pub fn set(&mut self, position: u64) -> Result<(), AccessTerminalError> {
+        self.position = position.min(self.total);
+        self.draw()
+    }
+
+    /// Advances the position by the given amount, drawing the bar if due.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    pub fn inc(&mut self, delta: u64) -> Result<(), AccessTerminalError> {
+        self.set(self.position.saturating_add(delta))
+    }
+
+    /// Renders the bar in its current state, fitted to the width of the terminal.
+    pub fn render(&self) -> String {
+        let percent = self.percent();
+        let suffix = format!("{percent:>3}% ({}/{})", self.position, self.total);
+        let fixed = self.label.chars().count() + suffix.len() + 4;
+        let width = self
+            .terminal
+            .size()
+            .columns
+            .saturating_sub(fixed + 1)
+            .min(MAX_BAR_WIDTH);
+        if width == 0 {
+            return format!("{} {suffix}", self.label);
+        }
+        let filled = usize::try_from(percent).unwrap_or(100) * width / 100;
+        format!(
+            "{} [{}{}] {suffix}",
+            self.label,
+            "#".repeat(filled),
+            "-".repeat(width - filled)
+        )
+    }
+
+    /// Completes the bar, printing its final state as an ordinary line (unless that exact line was
+    /// just logged). The position is left as is; call [`ProgressBar::set`] with the total
+    /// beforehand to show the work as complete.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    pub fn finish(mut self) -> Result<(), AccessTerminalError> {
+        self.finished = true;
+        if self.terminal.output_format().is_machine_readable() {
+            return Ok(());
+        }
+        let rendered = self.render();
+        if self.terminal.supports_redraw() {
+            self.terminal.clear_status()?;
+        } else if self.last_logged.as_ref() == Some(&rendered) {
+            return Ok(());
+        }
+        self.terminal.print_line(&rendered)
+    }
+
+    fn draw(&mut self) -> Result<(), AccessTerminalError> {
+        if self.terminal.output_format().is_machine_readable() {
+            return Ok(());
+        }
+        if self.terminal.supports_redraw() {
+            let now = Instant::now();
+            let due = self.last_draw.map_or(true, |last_draw| now.duration_since(last_draw) >= self.refresh);
+            if due {
+                self.last_draw = Some(now);
+                self.terminal.set_status(&self.render())?;
+            }
+        } else {
+            let step = self.percent() / LOG_STEP_PERCENT;
+            if self.last_logged_step.map_or(true, |last_step| step > last_step) {
+                let rendered = self.render();
+                self.terminal.set_status(&rendered)?;
+                self.last_logged_step = Some(step);
+                self.last_logged = Some(rendered);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Terminal + ?Sized> Drop for ProgressBar<'_, T> {
+    /// Clears the status line if the bar was abandoned without being finished (e.g., on error).
+    fn drop(&mut self) {
+        if !self.finished && self.last_draw.is_some() {
+            let _ = self.terminal.clear_status();
+        }
+    }
+}
+
+/// An indeterminate progress indicator, for work of an unknown amount; e.g., `| Connecting`.
+///
+/// On a terminal that supports redrawing, the spinner is animated on each
+/// [`Spinner::tick`], at most once per refresh interval. Otherwise, a line is printed on the first
+/// tick and then at most once per log interval, stating the elapsed time. On
+/// [`Spinner::finish`], the given outcome is printed as an ordinary line; e.g.,
+/// `Connecting: done`.
+pub struct Spinner<'t, T: Terminal + ?Sized> {
+    terminal: &'t mut T,
+    label: String,
+    refresh: Duration,
+    log_interval: Duration,
+    started: Instant,
+    frame: usize,
+    last_draw: Option<Instant>,
+    finished: bool,
+}
+
+impl<'t, T: Terminal + ?Sized> Spinner<'t, T> {
+    /// Creates a spinner with the given label. Nothing is drawn until the first
+    /// [`Spinner::tick`].
+    pub fn new(terminal: &'t mut T, label: impl Into<String>) -> Self {
+        Self {
+            terminal,
+            label: label.into(),
+            refresh: DEFAULT_REFRESH,
+            log_interval: DEFAULT_LOG_INTERVAL,
+            started: Instant::now(),
+            frame: 0,
+            last_draw: None,
+            finished: false,
+        }
+    }
+
+    /// Specifies the minimum interval between redraws. By default, it is [`DEFAULT_REFRESH`].
+    #[must_use]
+    pub fn with_refresh(mut self, refresh: Duration) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    /// Specifies the interval between lines on a terminal that does not support redrawing. By
+    /// default, it is [`DEFAULT_LOG_INTERVAL`].
+    #[must_use]
+    pub fn with_log_interval(mut self, log_interval: Duration) -> Self {
+        self.log_interval = log_interval;
+        self
+    }
+
+    /// Signals that the work is progressing, animating the spinner if due.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    pub fn tick(&mut self) -> Result<(), AccessTerminalError> {
+        if self.terminal.output_format().is_machine_readable() {
+            return Ok(());
+        }
+        let now = Instant::now();
+        let redraw = self.terminal.supports_redraw();
+        let interval = if redraw { self.refresh } else { self.log_interval };
+        if self.last_draw.map_or(false, |last_draw| now.duration_since(last_draw) < interval) {
+            return Ok(());
+        }
+
+        let status = if redraw {
+            let frame = SPINNER_FRAMES[self.frame % SPINNER_FRAMES.len()];
+            self.frame += 1;
+            format!("{frame} {}", self.label)
+        } else if self.last_draw.is_none() {
+            format!("{}...", self.label)
+        } else {
+            format!("{}... ({}s)", self.label, now.duration_since(self.started).as_secs())
+        };
+        self.last_draw = Some(now);
+        self.terminal.set_status(&status)
+    }
+
+    /// Completes the spinner, printing the label followed by the given outcome (e.g., `done`) as
+    /// an ordinary line.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    pub fn finish(mut self, outcome: &str) -> Result<(), AccessTerminalError> {
+        self.finished = true;
+        if self.terminal.output_format().is_machine_readable() {
+            return Ok(());
+        }
+        self.terminal.clear_status()?;
+        self.terminal.print_line(&format!("{}: {outcome}", self.label))
+    }
+}
+
+impl<T: Terminal + ?Sized> Drop for Spinner<'_, T> {
+    /// Clears the status line if the spinner was abandoned without being finished (e.g., on
+    /// error).
+    fn drop(&mut self) {
+        if !self.finished && self.last_draw.is_some() {
+            let _ = self.terminal.clear_status();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,192 @@
+// $coverage:ignore-start
+
+use crate::terminal::{Invocation, Mock, OutputFormat, ProgressBar, Spinner, Terminal, TerminalSize};
+use std::time::Duration;
+
+fn statuses(mock: &Mock) -> Vec<String> {
+    mock.invocations()
+        .iter()
+        .filter_map(|invocation| match invocation {
+            Invocation::Status(status) => Some(status.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn bar_plain_logs_every_step() {
+    let mut mock = Mock::default();
+    let mut bar = mock.progress_bar("Copying", 100);
+    for _ in 0..100 {
+        bar.inc(1).unwrap();
+    }
+    assert_eq!(100, bar.position());
+    bar.finish().unwrap();
+
+    let statuses = statuses(&mock);
+    assert_eq!(11, statuses.len());
+    assert_eq!("Copying [------------------------------]   1% (1/100)", statuses[0]);
+    assert_eq!("Copying [###---------------------------]  10% (10/100)", statuses[1]);
+    assert_eq!("Copying [##############################] 100% (100/100)", statuses[10]);
+    assert_eq!("", mock.output(), "already logged at 100%");
+}
+
+#[test]
+fn bar_plain_finish_incomplete() {
+    let mut mock = Mock::default();
+    let mut bar = ProgressBar::new(&mut mock, "Copying", 100);
+    bar.set(50).unwrap();
+    bar.set(73).unwrap();
+    bar.set(75).unwrap();
+    bar.finish().unwrap();
+
+    assert_eq!(vec![
+        Invocation::Status("Copying [###############---------------]  50% (50/100)".into()),
+        Invocation::Status("Copying [#####################---------]  73% (73/100)".into()),
+        Invocation::Print("Copying [######################--------]  75% (75/100)\n".into(), Ok(())),
+    ], mock.invocations());
+}
+
+#[test]
+fn bar_plain_finish_already_logged() {
+    let mut mock = Mock::default();
+    let mut bar = ProgressBar::new(&mut mock, "Copying", 10);
+    bar.set(7).unwrap();
+    bar.finish().unwrap();
+
+    assert_eq!(vec![
+        Invocation::Status("Copying [#####################---------]  70% (7/10)".into()),
+    ], mock.invocations());
+}
+
+#[test]
+fn bar_redraw() {
+    let mut mock = Mock::default().with_redraw(true).with_size(TerminalSize::new(40, 24));
+    let mut bar = mock.progress_bar("Copying", 4).with_refresh(Duration::ZERO);
+    for _ in 0..5 {
+        bar.inc(1).unwrap();
+    }
+    bar.finish().unwrap();
+
+    assert_eq!(vec![
+        Invocation::Status("Copying [####--------------]  25% (1/4)".into()),
+        Invocation::Status("Copying [#########---------]  50% (2/4)".into()),
+        Invocation::Status("Copying [#############-----]  75% (3/4)".into()),
+        Invocation::Status("Copying [##################] 100% (4/4)".into()),
+        Invocation::Status("Copying [##################] 100% (4/4)".into()),
+        Invocation::ClearStatus,
+        Invocation::Print("Copying [##################] 100% (4/4)\n".into(), Ok(())),
+    ], mock.invocations());
+}
+
+#[test]
+fn bar_redraw_throttled() {
+    let mut mock = Mock::default().with_redraw(true);
+    let mut bar = mock.progress_bar("Copying", 1000).with_refresh(Duration::from_secs(60));
+    for _ in 0..1000 {
+        bar.inc(1).unwrap();
+    }
+    drop(bar);
+
+    assert_eq!(vec![
+        Invocation::Status("Copying [------------------------------]   0% (1/1000)".into()),
+        Invocation::ClearStatus,
+    ], mock.invocations());
+}
+
+#[test]
+fn bar_render_narrow() {
+    let mut mock = Mock::default().with_size(TerminalSize::new(20, 24));
+    let mut bar = mock.progress_bar("Copying", 100);
+    bar.set(50).unwrap();
+    assert_eq!("Copying  50% (50/100)", bar.render());
+    assert_eq!(50, bar.percent());
+    assert_eq!(100, bar.total());
+}
+
+#[test]
+fn bar_empty() {
+    let mut mock = Mock::default();
+    let bar = mock.progress_bar("Nothing", 0);
+    assert_eq!(100, bar.percent());
+    bar.finish().unwrap();
+    assert_eq!("Nothing [##############################] 100% (0/0)\n", mock.output());
+}
+
+#[test]
+fn bar_huge() {
+    let mut mock = Mock::default();
+    let mut bar = mock.progress_bar("Hashing", u64::MAX);
+    bar.set(u64::MAX / 2).unwrap();
+    assert_eq!(49, bar.percent());
+    bar.inc(u64::MAX).unwrap();
+    assert_eq!(100, bar.percent());
+}
+
+#[test]
+fn bar_machine_readable() {
+    let mut mock = Mock::default().with_redraw(true).with_output_format(OutputFormat::Json);
+    let mut bar = mock.progress_bar("Copying", 10);
+    bar.set(5).unwrap();
+    bar.finish().unwrap();
+    assert!(mock.invocations().is_empty());
+}
+
+#[test]
+fn spinner_plain() {
+    let mut mock = Mock::default();
+    let mut spinner = mock.spinner("Connecting");
+    spinner.tick().unwrap();
+    spinner.tick().unwrap();
+    spinner.finish("done").unwrap();
+
+    assert_eq!(vec![
+        Invocation::Status("Connecting...".into()),
+        Invocation::ClearStatus,
+        Invocation::Print("Connecting: done\n".into(), Ok(())),
+    ], mock.invocations());
+}
+
+#[test]
+fn spinner_plain_periodic() {
+    let mut mock = Mock::default();
+    let mut spinner = Spinner::new(&mut mock, "Connecting").with_log_interval(Duration::ZERO);
+    spinner.tick().unwrap();
+    spinner.tick().unwrap();
+    drop(spinner);
+
+    assert_eq!(vec![
+        Invocation::Status("Connecting...".into()),
+        Invocation::Status("Connecting... (0s)".into()),
+        Invocation::ClearStatus,
+    ], mock.invocations());
+}
+
+#[test]
+fn spinner_redraw() {
+    let mut mock = Mock::default().with_redraw(true);
+    let mut spinner = mock.spinner("Connecting").with_refresh(Duration::ZERO);
+    for _ in 0..5 {
+        spinner.tick().unwrap();
+    }
+    spinner.finish("failed").unwrap();
+
+    assert_eq!(vec![
+        Invocation::Status("| Connecting".into()),
+        Invocation::Status("/ Connecting".into()),
+        Invocation::Status("- Connecting".into()),
+        Invocation::Status("\\ Connecting".into()),
+        Invocation::Status("| Connecting".into()),
+        Invocation::ClearStatus,
+        Invocation::Print("Connecting: failed\n".into(), Ok(())),
+    ], mock.invocations());
+}
+
+#[test]
+fn spinner_machine_readable() {
+    let mut mock = Mock::default().with_output_format(OutputFormat::Json);
+    let mut spinner = mock.spinner("Connecting");
+    spinner.tick().unwrap();
+    spinner.finish("done").unwrap();
+    assert!(mock.invocations().is_empty());
+}
@@ -0,0 +1,146 @@
+//! Presentation of errors to the user, with an optional input snippet, source chain and hint.
+
+use crate::terminal::{AccessTerminalError, Level, Style, Terminal, Value};
+use std::borrow::Cow;
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Range;
+
+/// A line of input with a byte span identifying the offending part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snippet {
+    pub line: String,
+    pub span: Range<usize>,
+}
+
+impl Snippet {
+    /// Renders a line of carets (`^`) underlining the span, preceded by enough spaces to align it
+    /// with the line. An empty span (e.g., pointing past the end of the line) yields a single caret.
+    pub fn underline(&self) -> String {
+        let columns = |range: Range<usize>| {
+            self.line.get(range).map_or(0, |s| s.chars().count())
+        };
+        let start = self.span.start.min(self.line.len());
+        let end = self.span.end.clamp(start, self.line.len());
+        let indent = columns(0..start);
+        let width = columns(start..end).max(1);
+        format!("{}{}", " ".repeat(indent), "^".repeat(width))
+    }
+}
+
+/// A printable account of an error: a title (e.g., `Command error`) and a message, followed by
+/// an optional snippet of the offending input, zero or more causes and an optional hint.
+///
+/// When printed, the title and message form the first line, rendered in the [`Level::Error`]
+/// style. The snippet (underlined with carets), each cause and the hint are printed on subsequent,
+/// indented lines. In a machine-readable output format, the report is emitted as a [`Value`]
+/// instead (see [`Report::to_value`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub title: Cow<'static, str>,
+    pub message: String,
+    pub snippet: Option<Snippet>,
+    pub causes: Vec<String>,
+    pub hint: Option<String>,
+}
+
+impl Report {
+    /// Creates a report with the given title and message, and no snippet, causes or hint.
+    pub fn new(title: impl Into<Cow<'static, str>>, message: impl Display) -> Self {
+        Self {
+            title: title.into(),
+            message: message.to_string(),
+            snippet: None,
+            causes: vec![],
+            hint: None,
+        }
+    }
+
+    /// Sets the snippet of the offending input, where `span` is a byte range into `line`.
+    #[must_use]
+    pub fn with_snippet(mut self, line: impl Into<String>, span: Range<usize>) -> Self {
+        self.snippet = Some(Snippet {
+            line: line.into(),
+            span,
+        });
+        self
+    }
+
+    /// Appends a cause.
+    #[must_use]
+    pub fn with_cause(mut self, cause: impl Display) -> Self {
+        self.causes.push(cause.to_string());
+        self
+    }
+
+    /// Appends the source chain of the given error as causes. (The error itself is not appended.)
+    /// A source that reads the same as the error it underlies is skipped; e.g., an
+    /// [`AccessTerminalError`] that repeats the message of its [`io::Error`](std::io::Error).
+    #[must_use]
+    pub fn with_sources_of(mut self, err: &(dyn Error + 'static)) -> Self {
+        let mut previous = err.to_string();
+        let mut source = err.source();
+        while let Some(err) = source {
+            let cause = err.to_string();
+            if cause != previous {
+                self.causes.push(cause.clone());
+            }
+            previous = cause;
+            source = err.source();
+        }
+        self
+    }
+
+    /// Sets the hint.
+    #[must_use]
+    pub fn with_hint(mut self, hint: impl Display) -> Self {
+        self.hint = Some(hint.to_string());
+        self
+    }
+
+    /// Converts the report into an object with the members `error` (the title), `message`,
+    /// `snippet` (an object with the `line`, `start` and `end` of the span, or `null`), `causes`
+    /// and `hint` (or `null`).
+    pub fn to_value(&self) -> Value {
+        let snippet = self.snippet.as_ref().map(|snippet| {
+            Value::object([
+                ("line", snippet.line.as_str().into()),
+                ("start", Value::Integer(i64::try_from(snippet.span.start).unwrap_or(i64::MAX))),
+                ("end", Value::Integer(i64::try_from(snippet.span.end).unwrap_or(i64::MAX))),
+            ])
+        });
+        Value::object([
+            ("error", self.title.as_ref().into()),
+            ("message", self.message.as_str().into()),
+            ("snippet", snippet.into()),
+            ("causes", self.causes.clone().into()),
+            ("hint", self.hint.clone().into()),
+        ])
+    }
+
+    /// Prints the report to the given terminal, or emits it as a [`Value`] if the terminal is in a
+    /// machine-readable output format.
+    ///
+    /// # Errors
+    /// If the terminal device could not be accessed for writing.
+    pub fn print(&self, terminal: &mut (impl Terminal + ?Sized)) -> Result<(), AccessTerminalError> {
+        if terminal.output_format().is_machine_readable() {
+            return terminal.emit(&self.to_value());
+        }
+        terminal.print_level(Level::Error, &format!("{}: {}.", self.title, self.message))?;
+        if let Some(snippet) = &self.snippet {
+            terminal.print_line(&format!("    {}", snippet.line))?;
+            terminal.print_level(Level::Error, &format!("    {}", snippet.underline()))?;
+        }
+        for cause in &self.causes {
+            terminal.print_line_styled(&format!("    caused by: {cause}"), Style::default().dim())?;
+        }
+        if let Some(hint) = &self.hint {
+            terminal.print_level(Level::Info, &format!("    hint: {hint}"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests;
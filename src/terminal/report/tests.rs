@@ -0,0 +1,111 @@
+// $coverage:ignore-start
+
+use std::io;
+use thiserror::Error;
+use crate::terminal::{AccessTerminalError, Invocation, Mock, OutputFormat, Report, Snippet, Value};
+
+#[test]
+fn print_plain() {
+    let mut mock = Mock::default();
+    Report::new("Command error", "cooling pump exploded")
+        .with_cause("pressure too high")
+        .with_hint("check the relief valve")
+        .print(&mut mock)
+        .unwrap();
+
+    assert_eq!(&[
+        Invocation::Print("Command error: cooling pump exploded.\n".into(), Ok(())),
+        Invocation::Print("    caused by: pressure too high\n".into(), Ok(())),
+        Invocation::Print("    hint: check the relief valve\n".into(), Ok(())),
+    ], mock.invocations());
+}
+
+#[test]
+fn print_ansi() {
+    let mut mock = Mock::default().with_ansi(true);
+    Report::new("Invalid input", "bad")
+        .with_cause("worse")
+        .with_hint("better")
+        .print(&mut mock)
+        .unwrap();
+
+    assert_eq!(&[
+        Invocation::Print("\x1b[1;31mInvalid input: bad.\x1b[0m\n".into(), Ok(())),
+        Invocation::Print("\x1b[2m    caused by: worse\x1b[0m\n".into(), Ok(())),
+        Invocation::Print("\x1b[36m    hint: better\x1b[0m\n".into(), Ok(())),
+    ], mock.invocations());
+}
+
+#[test]
+fn with_sources_of() {
+    let err = AccessTerminalError::from(io::Error::other("disk on fire"));
+    let report = Report::new("Command error", &err).with_sources_of(&err);
+    assert_eq!("disk on fire", report.message);
+    assert!(report.causes.is_empty());
+
+    let err = Wrapped(AccessTerminalError::from(io::Error::other("disk on fire")));
+    let report = Report::new("Command error", &err).with_sources_of(&err);
+    assert_eq!(vec!["disk on fire".to_string()], report.causes);
+
+    let report = Report::new("Command error", "no sources").with_sources_of(&io::Error::other("plain"));
+    assert!(report.causes.is_empty());
+    assert_eq!(None, report.hint);
+}
+
+#[derive(Debug, Error)]
+#[error("could not save")]
+struct Wrapped(#[source] AccessTerminalError);
+
+#[test]
+fn print_snippet() {
+    let mut mock = Mock::default();
+    Report::new("Invalid input", "invalid arguments to 'quit': 'foo'")
+        .with_snippet("quit foo", 5..8)
+        .print(&mut mock)
+        .unwrap();
+
+    assert_eq!(&[
+        Invocation::Print("Invalid input: invalid arguments to 'quit': 'foo'.\n".into(), Ok(())),
+        Invocation::Print("    quit foo\n".into(), Ok(())),
+        Invocation::Print("         ^^^\n".into(), Ok(())),
+    ], mock.invocations());
+}
+
+#[test]
+fn snippet_underline() {
+    let snippet = |line: &str, span| Snippet { line: line.into(), span };
+    assert_eq!("^^^^", snippet("quit", 0..4).underline());
+    assert_eq!("  ^", snippet("a b", 2..3).underline());
+    assert_eq!("    ^", snippet("quit", 4..4).underline());
+    assert_eq!("    ^", snippet("quit", 7..9).underline());
+    assert_eq!("^", snippet("", 0..0).underline());
+    assert_eq!("   ^^", snippet("éé ab", 5..7).underline());
+}
+
+#[test]
+fn to_value() {
+    let report = Report::new("Invalid input", "bad digit")
+        .with_snippet("add 1x", 4..6)
+        .with_cause("parse failure")
+        .with_hint("use decimal digits");
+    assert_eq!(Value::object([
+        ("error", "Invalid input".into()),
+        ("message", "bad digit".into()),
+        ("snippet", Value::object([("line", "add 1x".into()), ("start", 4.into()), ("end", 6.into())])),
+        ("causes", vec!["parse failure"].into()),
+        ("hint", "use decimal digits".into()),
+    ]), report.to_value());
+}
+
+#[test]
+fn print_json() {
+    let mut mock = Mock::default().with_output_format(OutputFormat::Json);
+    Report::new("Command error", "cooling pump exploded")
+        .with_cause("pressure too high")
+        .print(&mut mock)
+        .unwrap();
+    assert_eq!(
+        "{\"error\":\"Command error\",\"message\":\"cooling pump exploded\",\"snippet\":null,\"causes\":[\"pressure too high\"],\"hint\":null}\n",
+        mock.output()
+    );
+}
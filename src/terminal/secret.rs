@@ -0,0 +1,64 @@
+//! Sensitive values, such as passwords and tokens, read from the terminal.
+
+use std::fmt::{Debug, Display, Formatter};
+
+/// The placeholder shown in place of a [`Secret`] value.
+pub const REDACTED: &str = "<redacted>";
+
+/// A sensitive value read by [`Terminal::read_secret`](crate::terminal::Terminal::read_secret).
+///
+/// The value is never revealed by the [`Debug`] and [`Display`] implementations, which render
+/// [`REDACTED`] instead, so that it does not leak into logs, error messages or recordings.
+/// Use [`Secret::expose`] to access the value.
+#[derive(Clone, PartialEq, Eq, Default)]
+pub struct Secret(String);
+
+impl Secret {
+    /// Creates a [`Secret`] from the given value.
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// Creates a [`Secret`] from a line of input, stripping the trailing line terminator
+    /// (`\n` or `\r\n`), if present. Other whitespace is considered part of the value.
+    pub fn from_line(mut line: String) -> Self {
+        if line.ends_with('\n') {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+        Self(line)
+    }
+
+    /// Reveals the value.
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    /// Consumes the [`Secret`], returning the value.
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl Debug for Secret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Secret({REDACTED})")
+    }
+}
+
+impl Display for Secret {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REDACTED)
+    }
+}
+
+#[cfg(test)]
+mod tests;
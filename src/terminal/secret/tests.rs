@@ -0,0 +1,21 @@
+// $coverage:ignore-start
+
+use crate::terminal::{Secret, REDACTED};
+
+#[test]
+fn redacted() {
+    let secret = Secret::new("hunter2");
+    assert_eq!(REDACTED, secret.to_string());
+    assert_eq!(format!("Secret({REDACTED})"), format!("{secret:?}"));
+    assert_eq!("hunter2", secret.expose());
+    assert_eq!("hunter2", secret.into_inner());
+}
+
+#[test]
+fn from_line() {
+    assert_eq!("hunter2", Secret::from_line("hunter2\n".into()).expose());
+    assert_eq!("hunter2", Secret::from_line("hunter2\r\n".into()).expose());
+    assert_eq!(" hunter2 ", Secret::from_line(" hunter2 ".into()).expose());
+    assert_eq!("", Secret::from_line("\n".into()).expose());
+    assert_eq!(Secret::default(), Secret::from(String::new()));
+}
@@ -0,0 +1,25 @@
+//! Dimensions of the terminal, for adapting the layout of output to the available space.
+
+/// The dimensions of a terminal, in character cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalSize {
+    /// The number of columns (i.e., the width).
+    pub columns: usize,
+
+    /// The number of rows (i.e., the height).
+    pub rows: usize,
+}
+
+impl TerminalSize {
+    /// Creates a [`TerminalSize`] with the given dimensions.
+    pub fn new(columns: usize, rows: usize) -> Self {
+        Self { columns, rows }
+    }
+}
+
+impl Default for TerminalSize {
+    /// The conventional 80x24 terminal, assumed when the dimensions cannot be determined.
+    fn default() -> Self {
+        Self::new(80, 24)
+    }
+}
@@ -2,8 +2,10 @@
 //! traits. Out-of-the-box adapters exist for `stdin` and `stdout` streams. Adapters may be written
 //! to interface with nonstandard streams by supplying a custom closure.
 
-use crate::terminal::{AccessTerminalError, Terminal};
-use std::io;
+use crate::terminal::{
+    pager, stdout_supports_ansi, tty, AccessTerminalError, OutputFormat, Pager, Secret, Terminal,
+    TerminalSize,
+};
 use std::io::{stdin, stdout, Write};
 
 /// Terminal implementation over stream-like input/output abstractions.
@@ -26,9 +28,57 @@ impl<I: Input, O: Output> Terminal for Streaming<I, O> {
         self.output.print(s)
     }
 
+    fn page(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        if self.output.supports_paging() && self.input.supports_keys() && Pager::is_needed(s, self.size()) {
+            pager::run(self, s)
+        } else {
+            self.output.print(s)
+        }
+    }
+
+    fn supports_keys(&self) -> bool {
+        self.input.supports_keys()
+    }
+
+    fn read_key(&mut self) -> Result<Vec<u8>, AccessTerminalError> {
+        self.input.read_key()
+    }
+
     fn read_line(&mut self) -> Result<String, AccessTerminalError> {
         self.input.read_line()
     }
+
+    fn read_secret(&mut self) -> Result<Secret, AccessTerminalError> {
+        self.input.read_secret().map(Secret::from_line)
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.input.is_interactive()
+    }
+
+    fn supports_ansi(&self) -> bool {
+        self.output.supports_ansi()
+    }
+
+    fn size(&self) -> TerminalSize {
+        self.output.size()
+    }
+
+    fn output_format(&self) -> OutputFormat {
+        self.output.output_format()
+    }
+
+    fn supports_redraw(&self) -> bool {
+        self.output.supports_redraw()
+    }
+
+    fn set_status(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        self.output.set_status(s)
+    }
+
+    fn clear_status(&mut self) -> Result<(), AccessTerminalError> {
+        self.output.clear_status()
+    }
 }
 
 /// Piecewise abstraction over an input device.
@@ -39,39 +89,159 @@ pub trait Input {
     /// # Errors
     /// If the stream could not be accessed for reading.
     fn read_line(&mut self) -> Result<String, AccessTerminalError>;
+
+    /// Reads a complete line containing a sensitive value, without echoing it (where the stream
+    /// permits). The default implementation delegates to [`Self::read_line`].
+    ///
+    /// # Errors
+    /// If the stream could not be accessed for reading.
+    fn read_secret(&mut self) -> Result<String, AccessTerminalError> {
+        self.read_line()
+    }
+
+    /// Whether the stream is typed by a user, rather than piped or read from a file. The default
+    /// implementation returns `false`.
+    fn is_interactive(&self) -> bool {
+        false
+    }
+
+    /// Whether the stream delivers individual key presses. The default implementation returns
+    /// `false`.
+    fn supports_keys(&self) -> bool {
+        false
+    }
+
+    /// Reads a single key press, as the bytes delivered by a terminal in raw mode. The default
+    /// implementation returns an error.
+    ///
+    /// # Errors
+    /// If the stream could not be accessed for reading, or does not deliver key presses.
+    fn read_key(&mut self) -> Result<Vec<u8>, AccessTerminalError> {
+        Err(AccessTerminalError::other("key presses are not supported"))
+    }
 }
 
 /// Signature of a closure that implements the input side of the terminal device.
 pub type InputReader<'a> = Box<dyn FnMut() -> Result<String, AccessTerminalError> + 'a>;
 
+/// Signature of a closure that reads a single key press (see [`Input::read_key`]).
+pub type KeyReader<'a> = Box<dyn FnMut() -> Result<Vec<u8>, AccessTerminalError> + 'a>;
+
 /// Adapts an [`InputReader`] closure to the [`Input`] trait. The default adapter implementation
-/// delegates to `stdin`.
-pub struct InputAdapter<'a>(pub InputReader<'a>);
+/// delegates to `stdin`, returning [`AccessTerminalError::end_of_input`] once `stdin` is exhausted.
+/// Secrets are read from `stdin` with echo disabled if `stdin` is a terminal (on Unix-like systems).
+/// Key presses are read from `stdin` in raw mode if both `stdin` and `stdout` are interactive
+/// terminals. The input is deemed [interactive](Input::is_interactive) if `stdin` is a terminal.
+///
+/// This was formerly a tuple struct, `InputAdapter(pub InputReader)`, which is a breaking change
+/// from 0.2. In place of that constructor, use [`InputAdapter::new`] (or convert from an
+/// [`InputReader`]).
+pub struct InputAdapter<'a> {
+    reader: InputReader<'a>,
+    secret_reader: Option<InputReader<'a>>,
+    key_reader: Option<KeyReader<'a>>,
+    interactive: bool,
+}
+
+impl<'a> From<InputReader<'a>> for InputAdapter<'a> {
+    fn from(reader: InputReader<'a>) -> Self {
+        Self::new(reader)
+    }
+}
 
 impl<'a> InputAdapter<'a> {
-    /// Creates an [`InputAdapter`] over the given closure.
+    /// Creates an [`InputAdapter`] over the given closure, which is used for reading both ordinary
+    /// lines and secrets. The adapter is not [interactive](Input::is_interactive), unless specified
+    /// otherwise with [`InputAdapter::with_interactive`].
     pub fn new<F>(f: F) -> Self
     where
         F: FnMut() -> Result<String, AccessTerminalError> + 'a,
     {
-        Self(Box::new(f))
+        Self {
+            reader: Box::new(f),
+            secret_reader: None,
+            key_reader: None,
+            interactive: false,
+        }
+    }
+
+    /// Specifies a separate closure for reading secrets; e.g., one that disables echo.
+    #[must_use]
+    pub fn with_secret_reader<F>(mut self, f: F) -> Self
+    where
+        F: FnMut() -> Result<String, AccessTerminalError> + 'a,
+    {
+        self.secret_reader = Some(Box::new(f));
+        self
+    }
+
+    /// Specifies a closure for reading key presses (see [`Input::read_key`]). Without one, key
+    /// presses are not supported.
+    #[must_use]
+    pub fn with_key_reader<F>(mut self, f: F) -> Self
+    where
+        F: FnMut() -> Result<Vec<u8>, AccessTerminalError> + 'a,
+    {
+        self.key_reader = Some(Box::new(f));
+        self
+    }
+
+    /// Specifies whether the input is typed by a user (see [`Input::is_interactive`]).
+    #[must_use]
+    pub fn with_interactive(mut self, interactive: bool) -> Self {
+        self.interactive = interactive;
+        self
     }
 }
 
 impl Default for InputAdapter<'_> {
     fn default() -> Self {
-        Self(Box::new(|| {
-            let mut buf = String::default();
-            stdin().read_line(&mut buf)?;
-            Ok(buf)
-        })) // $coverage:ignore -- the closing brace eludes coverage
+        let adapter = Self::new(read_stdin_line)
+            .with_secret_reader(|| tty::without_echo(read_stdin_line))
+            .with_interactive(tty::stdin_is_terminal());
+        if tty::is_interactive() {
+            adapter.with_key_reader(tty::read_key)
+        } else {
+            adapter
+        }
     }
 }
 
 impl Input for InputAdapter<'_> {
     fn read_line(&mut self) -> Result<String, AccessTerminalError> {
-        self.0()
+        (self.reader)()
+    }
+
+    fn read_secret(&mut self) -> Result<String, AccessTerminalError> {
+        match &mut self.secret_reader {
+            Some(secret_reader) => secret_reader(),
+            None => (self.reader)(),
+        }
+    }
+
+    fn is_interactive(&self) -> bool {
+        self.interactive
     }
+
+    fn supports_keys(&self) -> bool {
+        self.key_reader.is_some()
+    }
+
+    fn read_key(&mut self) -> Result<Vec<u8>, AccessTerminalError> {
+        match &mut self.key_reader {
+            Some(key_reader) => key_reader(),
+            None => Err(AccessTerminalError::other("key presses are not supported")),
+        }
+    }
+}
+
+/// Reads a line from `stdin`, returning [`AccessTerminalError::end_of_input`] once `stdin` is exhausted.
+fn read_stdin_line() -> Result<String, AccessTerminalError> {
+    let mut buf = String::default();
+    if stdin().read_line(&mut buf)? == 0 {
+        return Err(AccessTerminalError::end_of_input());
+    }
+    Ok(buf)
 }
 
 /// Piecewise abstraction over an output device.
@@ -81,44 +251,207 @@ pub trait Output {
     /// # Errors
     /// If the stream could not be accessed for writing.
     fn print(&mut self, s: &str) -> Result<(), AccessTerminalError>;
+
+    /// Whether long output (see [`Terminal::page`]) may be presented in a [`Pager`], provided that
+    /// the input delivers key presses. The default implementation returns `false`.
+    fn supports_paging(&self) -> bool {
+        false
+    }
+
+    /// Whether the stream renders ANSI escape sequences. The default implementation returns `false`.
+    fn supports_ansi(&self) -> bool {
+        false
+    }
+
+    /// The dimensions of the device behind the stream. The default implementation returns
+    /// [`TerminalSize::default`].
+    fn size(&self) -> TerminalSize {
+        TerminalSize::default()
+    }
+
+    /// The format in which structured output is rendered. The default implementation returns
+    /// [`OutputFormat::Text`].
+    fn output_format(&self) -> OutputFormat {
+        OutputFormat::Text
+    }
+
+    /// Whether the stream can redraw a status line in place. The default implementation returns
+    /// `false`.
+    fn supports_redraw(&self) -> bool {
+        false
+    }
+
+    /// Shows a transient status line. The default implementation prints it as an ordinary line.
+    ///
+    /// # Errors
+    /// If the stream could not be accessed for writing.
+    fn set_status(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        let mut buf = s.to_owned();
+        buf.push('\n');
+        self.print(&buf)
+    }
+
+    /// Clears the status line, if one is shown. The default implementation does nothing.
+    ///
+    /// # Errors
+    /// If the stream could not be accessed for writing.
+    fn clear_status(&mut self) -> Result<(), AccessTerminalError> {
+        Ok(())
+    }
 }
 
 /// Signature of a closure that implements the output side of the terminal device.
 pub type OutputWriter<'a> = Box<dyn FnMut(&str) -> Result<(), AccessTerminalError> + 'a>;
 
 /// Adapts an [`OutputWriter`] closure to the [`Output`] trait. The default adapter implementation
-/// delegates to `stdout`.
-pub struct OutputAdapter<'a>(pub OutputWriter<'a>);
+/// delegates to `stdout`, enabling ANSI styling if [`stdout_supports_ansi`], and enabling the pager
+/// if both `stdin` and `stdout` are interactive terminals. Its size is queried from the terminal
+/// attached to `stdout` (following any resizing), falling back to [`TerminalSize::default`]. The
+/// status line is redrawn in place if `stdout` is a terminal capable of cursor control.
+///
+/// This was formerly a tuple struct, `OutputAdapter(pub OutputWriter)`, which is a breaking change
+/// from 0.2. In place of that constructor, use [`OutputAdapter::new`] (or convert from an
+/// [`OutputWriter`]).
+pub struct OutputAdapter<'a> {
+    writer: OutputWriter<'a>,
+    ansi: bool,
+    pager: bool,
+    size: Option<TerminalSize>,
+    format: OutputFormat,
+    redraw: bool,
+    status_shown: bool,
+}
+
+impl<'a> From<OutputWriter<'a>> for OutputAdapter<'a> {
+    fn from(writer: OutputWriter<'a>) -> Self {
+        Self::new(writer)
+    }
+}
 
 impl<'a> OutputAdapter<'a> {
-    /// Creates an [`OutputAdapter`] over the given closure.
+    /// Creates an [`OutputAdapter`] over the given closure. ANSI styling is disabled, and the size
+    /// is [`TerminalSize::default`].
     pub fn new<F>(f: F) -> Self
     where
         F: FnMut(&str) -> Result<(), AccessTerminalError> + 'a,
     {
-        Self(Box::new(f))
+        Self {
+            writer: Box::new(f),
+            ansi: false,
+            pager: false,
+            size: Some(TerminalSize::default()),
+            format: OutputFormat::Text,
+            redraw: false,
+            status_shown: false,
+        }
+    }
+
+    /// Specifies whether the underlying stream renders ANSI escape sequences.
+    #[must_use]
+    pub fn with_ansi(mut self, ansi: bool) -> Self {
+        self.ansi = ansi;
+        self
+    }
+
+    /// Specifies whether long output (see [`Terminal::page`]) is presented in a [`Pager`] when it
+    /// exceeds the height of the terminal. The pager reads key presses from the [`Input`] and
+    /// controls the screen with ANSI escape sequences, so it must only be enabled if the writer
+    /// prints to an interactive terminal.
+    #[must_use]
+    pub fn with_pager(mut self, pager: bool) -> Self {
+        self.pager = pager;
+        self
+    }
+
+    /// Specifies the dimensions of the device behind the stream. [`None`] queries the terminal
+    /// attached to `stdout` on each use, falling back to [`TerminalSize::default`] if it cannot be
+    /// determined.
+    #[must_use]
+    pub fn with_size(mut self, size: Option<TerminalSize>) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Specifies the format in which structured output is rendered. By default, it is
+    /// [`OutputFormat::Text`].
+    #[must_use]
+    pub fn with_output_format(mut self, format: OutputFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Specifies whether the status line (see [`Output::set_status`]) is redrawn in place, using
+    /// a carriage return and an ANSI erase sequence. This must only be enabled if the writer prints
+    /// to a terminal that is capable of cursor control; otherwise, each status is printed as an
+    /// ordinary line.
+    #[must_use]
+    pub fn with_redraw(mut self, redraw: bool) -> Self {
+        self.redraw = redraw;
+        self
     }
 }
 
+/// Returns the cursor to the start of the line and erases the line.
+const ERASE_LINE: &str = "\r\x1b[2K";
+
 impl Default for OutputAdapter<'_> {
     fn default() -> Self {
-        Self(Box::new(|str| {
+        Self::new(|str| {
             print!("{str}");
             stdout().flush()?;
             Ok(())
-        }))
+        })
+        .with_ansi(stdout_supports_ansi())
+        .with_pager(tty::is_interactive())
+        .with_size(None)
+        .with_redraw(tty::stdout_controls_cursor())
     }
 }
 
 impl Output for OutputAdapter<'_> {
     fn print(&mut self, s: &str) -> Result<(), AccessTerminalError> {
-        self.0(s)
+        self.clear_status()?;
+        (self.writer)(s)
+    }
+
+    fn supports_paging(&self) -> bool {
+        self.pager
+    }
+
+    fn supports_ansi(&self) -> bool {
+        self.ansi
+    }
+
+    fn size(&self) -> TerminalSize {
+        self.size.or_else(tty::size).unwrap_or_default()
     }
-}
 
-impl From<io::Error> for AccessTerminalError {
-    fn from(err: io::Error) -> Self {
-        Self(err.to_string())
+    fn output_format(&self) -> OutputFormat {
+        self.format
+    }
+
+    fn supports_redraw(&self) -> bool {
+        self.redraw
+    }
+
+    fn set_status(&mut self, s: &str) -> Result<(), AccessTerminalError> {
+        if !self.redraw {
+            return self.print(&format!("{s}\n"));
+        }
+        // truncate to fit on one line, as a wrapped line would not be erased by the next redraw
+        let width = self.size().columns.saturating_sub(1);
+        let status = s.lines().next().unwrap_or_default().chars().take(width).collect::<String>();
+        (self.writer)(&format!("{ERASE_LINE}{status}"))?;
+        self.status_shown = true;
+        Ok(())
+    }
+
+    fn clear_status(&mut self) -> Result<(), AccessTerminalError> {
+        if self.status_shown {
+            self.status_shown = false;
+            (self.writer)(ERASE_LINE)?;
+        }
+        Ok(())
     }
 }
 
@@ -2,10 +2,11 @@
 
 use std::fs::File;
 use std::{io};
+use std::error::Error;
 use std::io::{BufRead, BufReader, Cursor, ErrorKind, Write};
 use flanker_temp::TempPath;
 use stdio_override::{StdinOverride};
-use crate::terminal::{AccessTerminalError, InputAdapter, OutputAdapter, Streaming, Terminal};
+use crate::terminal::{AccessTerminalError, AccessTerminalErrorKind, InputAdapter, InputReader, OutputAdapter, OutputFormat, OutputWriter, Streaming, Style, Terminal, TerminalSize};
 
 #[test]
 fn default_print_no_locking() {
@@ -29,6 +30,7 @@ fn default_read_line_intercepted() {
     assert_eq!("line one\n", line);
     let line = term.read_line().unwrap();
     assert_eq!("line two", line);
+    assert_eq!(AccessTerminalError::end_of_input(), term.read_line().unwrap_err());
     drop(guard);
 }
 
@@ -53,9 +55,189 @@ fn input_output() {
     assert_eq!("printed", written);
 }
 
+#[test]
+fn input_adapter_from_reader() {
+    let reader: InputReader = Box::new(|| Ok("hello".into()));
+    let mut term = Streaming {
+        input: InputAdapter::from(reader), output: OutputAdapter::new(|_| Ok(()))
+    };
+    assert_eq!("hello", term.read_line().unwrap());
+}
+
+#[test]
+fn output_adapter_from_writer() {
+    let mut written = String::default();
+    let writer: OutputWriter = Box::new(|str| {
+        written.push_str(str);
+        Ok(())
+    });
+    let mut term = Streaming {
+        input: InputAdapter::default(), output: OutputAdapter::from(writer)
+    };
+    term.print("printed").unwrap();
+    drop(term);
+
+    assert_eq!("printed", written);
+}
+
+#[test]
+fn output_supports_ansi() {
+    let mut write = Cursor::new(Vec::new());
+    let output = OutputAdapter::new(|str| Ok(write!(write, "{}", str)?));
+    let mut term = Streaming {
+        input: InputAdapter::default(), output
+    };
+    assert!(!term.supports_ansi());
+    term.print_styled("plain", Style::default().bold()).unwrap();
+
+    term.output = OutputAdapter::new(|_| Ok(())).with_ansi(true);
+    assert!(term.supports_ansi());
+    drop(term);
+
+    assert_eq!("plain", String::from_utf8(write.into_inner()).unwrap());
+}
+
 #[test]
 fn implements_from_io_error() {
     let io_error = io::Error::new(ErrorKind::BrokenPipe, "broken pipe");
     let access_error = AccessTerminalError::from(io_error);
     assert!(access_error.to_string().contains("broken pipe"));
-}
\ No newline at end of file
+    assert_eq!(AccessTerminalErrorKind::BrokenPipe, access_error.kind());
+    assert_eq!(Some(ErrorKind::BrokenPipe), access_error.io_kind());
+    let source = access_error.source().unwrap();
+    assert_eq!("broken pipe", source.to_string());
+}
+
+#[test]
+fn read_line_invalid_utf8() {
+    let mut read = BufReader::new(Cursor::new(&[0xff, 0xfe, b'\n'][..]));
+    let input = InputAdapter::new(|| {
+        let mut buf = String::default();
+        read.read_line(&mut buf)?;
+        Ok(buf)
+    });
+    let mut term = Streaming {
+        input, output: OutputAdapter::default()
+    };
+    assert_eq!(AccessTerminalErrorKind::Encoding, term.read_line().unwrap_err().kind());
+}
+
+#[test]
+fn read_secret() {
+    let input = InputAdapter::new(|| Ok("line\n".into()));
+    let mut term = Streaming {
+        input, output: OutputAdapter::default()
+    };
+    assert_eq!("line", term.read_secret().unwrap().expose());
+
+    term.input = InputAdapter::new(|| Ok("line\n".into())).with_secret_reader(|| Ok("secret\r\n".into()));
+    assert_eq!("line\n", term.read_line().unwrap());
+    assert_eq!("secret", term.read_secret().unwrap().expose());
+}
+
+#[test]
+fn page_without_pager() {
+    let mut write = Cursor::new(Vec::new());
+    let output = OutputAdapter::new(|str| Ok(write!(write, "{}", str)?)).with_pager(false);
+    let mut term = Streaming {
+        input: InputAdapter::default(), output
+    };
+    term.page("a\nb\nc\n").unwrap();
+    drop(term);
+
+    assert_eq!("a\nb\nc\n", String::from_utf8(write.into_inner()).unwrap());
+}
+
+#[test]
+fn page_with_pager() {
+    let mut written = String::default();
+    let output = OutputAdapter::new(|str| {
+        written.push_str(str);
+        Ok(())
+    })
+    .with_pager(true)
+    .with_size(Some(TerminalSize::new(80, 3)));
+    let mut term = Streaming {
+        input: InputAdapter::new(|| Ok(String::default())), output
+    };
+    assert!(!term.supports_keys());
+    term.page("a\nb\nc\n").unwrap();
+
+    let mut keys = vec![b"q".to_vec()].into_iter();
+    term.input = InputAdapter::new(|| Ok(String::default())).with_key_reader(move || Ok(keys.next().unwrap()));
+    assert!(term.supports_keys());
+    term.page("a\nb\n").unwrap();
+    term.page("a\nb\nc\n").unwrap();
+    drop(term);
+
+    assert_eq!(
+        "a\nb\nc\na\nb\n\x1b[?1049h\x1b[H\x1b[2Ja\nb\nlines 1-2 of 3 (space: next page, b: previous page, q: quit)\x1b[?1049l",
+        written
+    );
+}
+
+#[test]
+fn size() {
+    let output = OutputAdapter::new(|_| Ok(()));
+    let mut term = Streaming {
+        input: InputAdapter::default(), output
+    };
+    assert_eq!(TerminalSize::default(), term.size());
+
+    term.output = OutputAdapter::new(|_| Ok(())).with_size(Some(TerminalSize::new(132, 43)));
+    assert_eq!(TerminalSize::new(132, 43), term.size());
+}
+
+#[test]
+fn output_format() {
+    let output = OutputAdapter::new(|_| Ok(()));
+    let mut term = Streaming {
+        input: InputAdapter::default(), output
+    };
+    assert_eq!(OutputFormat::Text, term.output_format());
+
+    term.output = OutputAdapter::new(|_| Ok(())).with_output_format(OutputFormat::Json);
+    assert_eq!(OutputFormat::Json, term.output_format());
+}
+
+#[test]
+fn status_redraw() {
+    let mut write = Cursor::new(Vec::new());
+    let output = OutputAdapter::new(|str| Ok(write!(write, "{}", str)?))
+        .with_redraw(true)
+        .with_size(Some(TerminalSize::new(8, 24)));
+    let mut term = Streaming {
+        input: InputAdapter::default(), output
+    };
+    assert!(term.supports_redraw());
+    term.clear_status().unwrap();
+    term.set_status("one").unwrap();
+    term.set_status("a rather long status\nsecond line").unwrap();
+    term.print("text\n").unwrap();
+    term.print("more\n").unwrap();
+    term.set_status("two").unwrap();
+    term.clear_status().unwrap();
+    term.clear_status().unwrap();
+    drop(term);
+
+    assert_eq!(
+        "\r\x1b[2Kone\r\x1b[2Ka rathe\r\x1b[2Ktext\nmore\n\r\x1b[2Ktwo\r\x1b[2K",
+        String::from_utf8(write.into_inner()).unwrap()
+    );
+}
+
+#[test]
+fn status_without_redraw() {
+    let mut write = Cursor::new(Vec::new());
+    let output = OutputAdapter::new(|str| Ok(write!(write, "{}", str)?));
+    let mut term = Streaming {
+        input: InputAdapter::default(), output
+    };
+    assert!(!term.supports_redraw());
+    term.set_status("one").unwrap();
+    term.clear_status().unwrap();
+    term.set_status("two").unwrap();
+    drop(term);
+
+    assert_eq!("one\ntwo\n", String::from_utf8(write.into_inner()).unwrap());
+}
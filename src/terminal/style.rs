@@ -0,0 +1,183 @@
+//! Styling of terminal output: semantic [`Level`]s and raw [`Style`]s, rendered as ANSI escape
+//! sequences on capable terminals (see [`Terminal::supports_ansi`](crate::terminal::Terminal::supports_ansi)).
+
+use crate::terminal::tty;
+use std::env;
+use std::ffi::OsStr;
+
+/// A foreground colour from the standard 16-colour ANSI palette.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    BrightBlack,
+    BrightRed,
+    BrightGreen,
+    BrightYellow,
+    BrightBlue,
+    BrightMagenta,
+    BrightCyan,
+    BrightWhite,
+}
+
+impl Colour {
+    fn sgr(self) -> u8 {
+        match self {
+            Colour::Black => 30,
+            Colour::Red => 31,
+            Colour::Green => 32,
+            Colour::Yellow => 33,
+            Colour::Blue => 34,
+            Colour::Magenta => 35,
+            Colour::Cyan => 36,
+            Colour::White => 37,
+            Colour::BrightBlack => 90,
+            Colour::BrightRed => 91,
+            Colour::BrightGreen => 92,
+            Colour::BrightYellow => 93,
+            Colour::BrightBlue => 94,
+            Colour::BrightMagenta => 95,
+            Colour::BrightCyan => 96,
+            Colour::BrightWhite => 97,
+        }
+    }
+}
+
+/// A combination of text attributes. The default style is plain text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    pub fg: Option<Colour>,
+    pub bold: bool,
+    pub dim: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub reverse: bool,
+}
+
+impl Style {
+    /// Sets the foreground colour.
+    #[must_use]
+    pub fn fg(mut self, colour: Colour) -> Self {
+        self.fg = Some(colour);
+        self
+    }
+
+    /// Sets the bold attribute.
+    #[must_use]
+    pub fn bold(mut self) -> Self {
+        self.bold = true;
+        self
+    }
+
+    /// Sets the dim (faint) attribute.
+    #[must_use]
+    pub fn dim(mut self) -> Self {
+        self.dim = true;
+        self
+    }
+
+    /// Sets the italic attribute.
+    #[must_use]
+    pub fn italic(mut self) -> Self {
+        self.italic = true;
+        self
+    }
+
+    /// Sets the underline attribute.
+    #[must_use]
+    pub fn underline(mut self) -> Self {
+        self.underline = true;
+        self
+    }
+
+    /// Sets the reverse video attribute, swapping the foreground and background colours.
+    #[must_use]
+    pub fn reverse(mut self) -> Self {
+        self.reverse = true;
+        self
+    }
+
+    /// Is this plain text, without any attributes?
+    pub fn is_plain(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Encloses the given string slice in the ANSI escape sequences for this style. A plain style
+    /// returns the string unchanged.
+    pub fn paint(&self, s: &str) -> String {
+        if self.is_plain() {
+            return s.into();
+        }
+
+        let mut codes = vec![];
+        if self.bold {
+            codes.push(1);
+        }
+        if self.dim {
+            codes.push(2);
+        }
+        if self.italic {
+            codes.push(3);
+        }
+        if self.underline {
+            codes.push(4);
+        }
+        if self.reverse {
+            codes.push(7);
+        }
+        if let Some(fg) = self.fg {
+            codes.push(fg.sgr());
+        }
+        let codes = codes.iter().map(ToString::to_string).collect::<Vec<_>>().join(";");
+        format!("\x1b[{codes}m{s}\x1b[0m")
+    }
+}
+
+/// The semantic level of a message, which determines its [`Style`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Info,
+    Warning,
+    Error,
+    Success,
+}
+
+impl Level {
+    /// The style in which messages of this level are rendered.
+    pub fn style(self) -> Style {
+        match self {
+            Level::Info => Style::default().fg(Colour::Cyan),
+            Level::Warning => Style::default().fg(Colour::Yellow),
+            Level::Error => Style::default().fg(Colour::Red).bold(),
+            Level::Success => Style::default().fg(Colour::Green),
+        }
+    }
+}
+
+/// Determines whether ANSI styling should be used for `stdout`: it must be a terminal, the
+/// `NO_COLOR` environment variable must be unset (or empty), and `TERM` must not be `dumb`.
+pub fn stdout_supports_ansi() -> bool {
+    tty::stdout_is_terminal() && env_permits_ansi()
+}
+
+/// Checks the `NO_COLOR` and `TERM` environment variables; see [`stdout_supports_ansi`].
+pub fn env_permits_ansi() -> bool {
+    permits_ansi(env::var_os("NO_COLOR").as_deref(), env::var_os("TERM").as_deref())
+}
+
+/// Checks the given values of the `NO_COLOR` and `TERM` environment variables ([`None`] if unset);
+/// see [`stdout_supports_ansi`].
+fn permits_ansi(no_color: Option<&OsStr>, term: Option<&OsStr>) -> bool {
+    let no_color = no_color.map_or(false, |val| !val.is_empty());
+    let dumb = term.map_or(false, |val| val == "dumb");
+    !no_color && !dumb
+}
+
+#[cfg(test)]
+mod tests;
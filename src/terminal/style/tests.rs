@@ -0,0 +1,38 @@
+// $coverage:ignore-start
+
+use std::ffi::OsStr;
+use crate::terminal::style::permits_ansi;
+use crate::terminal::{Colour, Level, Style};
+
+#[test]
+fn paint_plain() {
+    assert!(Style::default().is_plain());
+    assert_eq!("text", Style::default().paint("text"));
+}
+
+#[test]
+fn paint_attributes() {
+    assert_eq!("\x1b[1mtext\x1b[0m", Style::default().bold().paint("text"));
+    assert_eq!("\x1b[2;3;4mtext\x1b[0m", Style::default().dim().italic().underline().paint("text"));
+    assert_eq!("\x1b[7mtext\x1b[0m", Style::default().reverse().paint("text"));
+    assert_eq!("\x1b[1;92mtext\x1b[0m", Style::default().fg(Colour::BrightGreen).bold().paint("text"));
+    assert_eq!("\x1b[30mtext\x1b[0m", Style::default().fg(Colour::Black).paint("text"));
+}
+
+#[test]
+fn level_styles() {
+    assert_eq!(Style::default().fg(Colour::Cyan), Level::Info.style());
+    assert_eq!(Style::default().fg(Colour::Yellow), Level::Warning.style());
+    assert_eq!(Style::default().fg(Colour::Red).bold(), Level::Error.style());
+    assert_eq!(Style::default().fg(Colour::Green), Level::Success.style());
+}
+
+#[test]
+fn permits_ansi_honours_no_color_and_term() {
+    let val = |s| Some(OsStr::new(s));
+    assert!(permits_ansi(None, None));
+    assert!(permits_ansi(None, val("xterm")));
+    assert!(permits_ansi(val(""), val("xterm")));
+    assert!(!permits_ansi(val("1"), val("xterm")));
+    assert!(!permits_ansi(None, val("dumb")));
+}
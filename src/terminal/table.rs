@@ -0,0 +1,274 @@
+//! Tabular output for commands. A [`Table`] holds rows of data under a set of [`Column`]s, and is
+//! rendered according to the [`OutputFormat`] of the terminal: as a console table that fits the
+//! terminal width, or as CSV or JSON for machine consumption.
+
+use crate::terminal::format::csv_field;
+use crate::terminal::{OutputFormat, TerminalSize, Value};
+use stanza::renderer::console::{Console, Decor};
+use stanza::renderer::Renderer;
+use stanza::style::{Bold, HAlign, Header, MaxWidth, Styles};
+use stanza::table::{Col, Row};
+use std::borrow::Cow;
+
+/// The ellipsis that marks a truncated cell.
+const ELLIPSIS: char = '…';
+
+/// The narrowest that a column is squeezed to when fitting a table to the terminal width.
+const MIN_FITTED_WIDTH: usize = 4;
+
+/// Horizontal alignment of the cells in a [`Column`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Centre,
+    Right,
+}
+
+impl From<Align> for HAlign {
+    fn from(align: Align) -> Self {
+        match align {
+            Align::Left => HAlign::Left,
+            Align::Centre => HAlign::Centred,
+            Align::Right => HAlign::Right,
+        }
+    }
+}
+
+/// The specification of a column in a [`Table`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    header: Cow<'static, str>,
+    align: Align,
+    max_width: Option<usize>,
+}
+
+impl Column {
+    /// Creates a left-aligned column with the given header, the width of which is unconstrained.
+    pub fn new(header: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            header: header.into(),
+            align: Align::default(),
+            max_width: None,
+        }
+    }
+
+    /// Specifies the alignment of the cells in the column.
+    #[must_use]
+    pub fn with_align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+
+    /// Specifies the maximum width of the column, in characters. Longer cells are truncated
+    /// (with an ellipsis) when rendered as text; machine-readable formats are unaffected.
+    ///
+    /// # Panics
+    /// If `max_width` is zero, as even a truncated cell occupies one character.
+    #[must_use]
+    pub fn with_max_width(mut self, max_width: usize) -> Self {
+        assert!(max_width > 0, "the maximum width of a column must be at least 1");
+        self.max_width = Some(max_width);
+        self
+    }
+
+    /// The header of the column.
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    /// The alignment of the cells in the column.
+    pub fn align(&self) -> Align {
+        self.align
+    }
+
+    /// The maximum width of the column, if set.
+    pub fn max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+}
+
+/// Rows of data under a set of [`Column`]s, printed with
+/// [`Terminal::print_table`](crate::terminal::Terminal::print_table).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Creates an empty table with the given columns.
+    ///
+    /// # Panics
+    /// If `columns` is empty.
+    pub fn new(columns: Vec<Column>) -> Self {
+        assert!(!columns.is_empty(), "a table must have at least one column");
+        Self {
+            columns,
+            rows: Vec::default(),
+        }
+    }
+
+    /// Appends a row to the table.
+    ///
+    /// # Panics
+    /// If the number of cells differs from the number of columns.
+    #[must_use]
+    pub fn with_row<S: ToString>(mut self, cells: impl IntoIterator<Item = S>) -> Self {
+        self.push_row(cells);
+        self
+    }
+
+    /// Appends a row to the table.
+    ///
+    /// # Panics
+    /// If the number of cells differs from the number of columns.
+    pub fn push_row<S: ToString>(&mut self, cells: impl IntoIterator<Item = S>) {
+        let row = cells.into_iter().map(|cell| cell.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            self.columns.len(),
+            row.len(),
+            "row has {} cells, but the table has {} columns",
+            row.len(),
+            self.columns.len()
+        );
+        self.rows.push(row);
+    }
+
+    /// The columns of the table.
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// The rows of the table.
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    /// Renders the table in the given format. The `size` and `ansi` arguments only apply to
+    /// [`OutputFormat::Text`], which renders a console table that fits within the width of the
+    /// terminal (wrapping the content of the widest columns if necessary), styled with ANSI escape
+    /// sequences if `ansi` is set.
+    pub fn render(&self, format: OutputFormat, size: TerminalSize, ansi: bool) -> String {
+        match format {
+            OutputFormat::Text => self.render_text(size.columns, ansi),
+            OutputFormat::Csv => self.render_csv(),
+            OutputFormat::Json => self.render_json(),
+        }
+    }
+
+    fn render_text(&self, columns: usize, ansi: bool) -> String {
+        let rows = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&self.columns)
+                    .map(|(cell, column)| match column.max_width {
+                        Some(max_width) => truncate(cell, max_width),
+                        None => cell.into(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut widths = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(col, column)| {
+                rows.iter()
+                    .flat_map(|row| row[col].lines())
+                    .chain(column.header.lines())
+                    .map(|line| line.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect::<Vec<_>>();
+        fit(&mut widths, columns.saturating_sub(self.columns.len() + 1));
+
+        let mut table = stanza::table::Table::default()
+            .with_cols(
+                self.columns
+                    .iter()
+                    .zip(&widths)
+                    .map(|(column, &width)| {
+                        Col::new(
+                            Styles::default()
+                                .with(HAlign::from(column.align))
+                                .with(MaxWidth(width)),
+                        )
+                    })
+                    .collect(),
+            )
+            .with_row(Row::new(
+                Styles::default().with(Header(true)).with(Bold(true)),
+                self.columns.iter().map(|column| column.header().into()).collect(),
+            ));
+        for row in rows {
+            table.push_row(row);
+        }
+
+        let mut decor = Decor::default().suppress_inner_horizontal_border();
+        if !ansi {
+            decor = decor.suppress_escape_codes();
+        }
+        format!("{}\n", Console(decor).render(&table))
+    }
+
+    fn render_csv(&self) -> String {
+        let mut buf = csv_record(self.columns.iter().map(Column::header));
+        for row in &self.rows {
+            buf.push_str(&csv_record(row.iter().map(String::as_str)));
+        }
+        buf
+    }
+
+    fn render_json(&self) -> String {
+        let mut buf = String::new();
+        for row in &self.rows {
+            let members = self.columns.iter().map(Column::header).zip(row.iter().map(String::as_str));
+            buf.push_str(&Value::object(members.map(|(header, cell)| (header, cell.into()))).to_json());
+            buf.push('\n');
+        }
+        buf
+    }
+}
+
+/// Encodes the given fields as a CSV record, terminated by a line break.
+fn csv_record<'f>(fields: impl Iterator<Item = &'f str>) -> String {
+    let mut record = fields.map(csv_field).collect::<Vec<_>>().join(",");
+    record.push('\n');
+    record
+}
+
+/// Truncates each line of the given cell to `max_width` characters, marking any truncation
+/// with an ellipsis.
+fn truncate(cell: &str, max_width: usize) -> String {
+    cell.lines()
+        .map(|line| {
+            if line.chars().count() <= max_width {
+                line.into()
+            } else {
+                let mut truncated = line.chars().take(max_width.saturating_sub(1)).collect::<String>();
+                truncated.push(ELLIPSIS);
+                truncated
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Narrows the widest of the given column widths, one character at a time, until their sum fits
+/// within `available`, or until no column can be narrowed past [`MIN_FITTED_WIDTH`].
+fn fit(widths: &mut [usize], available: usize) {
+    while widths.iter().sum::<usize>() > available {
+        match widths.iter_mut().filter(|width| **width > MIN_FITTED_WIDTH).max() {
+            Some(widest) => *widest -= 1,
+            None => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests;
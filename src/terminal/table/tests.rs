@@ -0,0 +1,101 @@
+// $coverage:ignore-start
+
+use crate::terminal::{Align, Column, OutputFormat, Table, TerminalSize};
+
+fn sample() -> Table {
+    Table::new(vec![
+        Column::new("Name"),
+        Column::new("Size").with_align(Align::Right),
+        Column::new("Notes").with_max_width(12),
+    ])
+    .with_row(["alpha", "1", "first"])
+    .with_row(["beta, gamma", "1024", "a \"quoted\" note that is long"])
+}
+
+#[test]
+fn render_text() {
+    assert_eq!("\
+    ╔═══════════╤════╤════════════╗\n\
+    ║Name       │Size│Notes       ║\n\
+    ║alpha      │   1│first       ║\n\
+    ║beta, gamma│1024│a \"quoted\" …║\n\
+    ╚═══════════╧════╧════════════╝\n", sample().render(OutputFormat::Text, TerminalSize::default(), false));
+}
+
+#[test]
+fn render_text_fits_width() {
+    let rendered = sample().render(OutputFormat::Text, TerminalSize::new(30, 24), false);
+    assert_eq!("\
+    ╔═══════════╤════╤═══════════╗\n\
+    ║Name       │Size│Notes      ║\n\
+    ║alpha      │   1│first      ║\n\
+    ║beta, gamma│1024│a \"quoted\" ║\n\
+    ║           │    │…          ║\n\
+    ╚═══════════╧════╧═══════════╝\n", rendered);
+
+    // columns are not squeezed past a minimum width, even if the table overflows
+    let rendered = sample().render(OutputFormat::Text, TerminalSize::new(5, 24), false);
+    assert!(rendered.lines().all(|line| line.chars().count() == 16));
+}
+
+#[test]
+fn render_text_ansi() {
+    let plain = sample().render(OutputFormat::Text, TerminalSize::default(), false);
+    assert!(!plain.contains('\x1b'));
+    let styled = sample().render(OutputFormat::Text, TerminalSize::default(), true);
+    assert!(styled.contains('\x1b'));
+}
+
+#[test]
+fn render_csv() {
+    assert_eq!("\
+    Name,Size,Notes\n\
+    alpha,1,first\n\
+    \"beta, gamma\",1024,\"a \"\"quoted\"\" note that is long\"\n", sample().render(OutputFormat::Csv, TerminalSize::new(5, 24), true));
+}
+
+#[test]
+fn render_json() {
+    assert_eq!("\
+    {\"Name\":\"alpha\",\"Size\":\"1\",\"Notes\":\"first\"}\n\
+    {\"Name\":\"beta, gamma\",\"Size\":\"1024\",\"Notes\":\"a \\\"quoted\\\" note that is long\"}\n", sample().render(OutputFormat::Json, TerminalSize::new(5, 24), true));
+}
+
+#[test]
+fn render_empty() {
+    let table = Table::new(vec![Column::new("Name")]);
+    assert_eq!("\
+    ╔════╗\n\
+    ║Name║\n\
+    ╚════╝\n", table.render(OutputFormat::Text, TerminalSize::default(), false));
+    assert_eq!("Name\n", table.render(OutputFormat::Csv, TerminalSize::default(), false));
+    assert_eq!("", table.render(OutputFormat::Json, TerminalSize::default(), false));
+}
+
+#[test]
+fn columns_and_rows() {
+    let mut table = Table::new(vec![Column::new("Id").with_align(Align::Centre).with_max_width(3)]);
+    table.push_row([42]);
+    assert_eq!("Id", table.columns()[0].header());
+    assert_eq!(Align::Centre, table.columns()[0].align());
+    assert_eq!(Some(3), table.columns()[0].max_width());
+    assert_eq!(&[vec!["42".to_string()]], table.rows());
+}
+
+#[test]
+#[should_panic(expected = "row has 1 cells, but the table has 2 columns")]
+fn push_row_mismatch() {
+    let _ = Table::new(vec![Column::new("A"), Column::new("B")]).with_row(["a"]);
+}
+
+#[test]
+#[should_panic(expected = "a table must have at least one column")]
+fn new_without_columns() {
+    let _ = Table::new(vec![]);
+}
+
+#[test]
+#[should_panic(expected = "the maximum width of a column must be at least 1")]
+fn max_width_zero() {
+    let _ = Column::new("A").with_max_width(0);
+}
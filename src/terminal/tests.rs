@@ -1,6 +1,31 @@
 // $coverage:ignore-start
 
-use crate::terminal::{AccessTerminalError, Invocation, Mock, mock, Terminal};
+use crate::terminal::{AccessTerminalError, AccessTerminalErrorKind, Column, Invocation, Level, Mock, mock, OutputFormat, Style, Table, Terminal, TerminalSize, Value};
+use std::error::Error;
+use std::io::{self, ErrorKind};
+
+#[test]
+fn from_io_error_kinds() {
+    fn kind_of(kind: ErrorKind) -> AccessTerminalErrorKind {
+        AccessTerminalError::from(io::Error::from(kind)).kind()
+    }
+    assert_eq!(AccessTerminalErrorKind::EndOfInput, kind_of(ErrorKind::UnexpectedEof));
+    assert_eq!(AccessTerminalErrorKind::Interrupted, kind_of(ErrorKind::Interrupted));
+    assert_eq!(AccessTerminalErrorKind::Encoding, kind_of(ErrorKind::InvalidData));
+    assert_eq!(AccessTerminalErrorKind::Io(ErrorKind::PermissionDenied), kind_of(ErrorKind::PermissionDenied));
+}
+
+#[test]
+fn other_error() {
+    let err = AccessTerminalError::other("meltdown");
+    assert_eq!(AccessTerminalErrorKind::Other, err.kind());
+    assert_eq!("meltdown", err.message());
+    assert_eq!(None, err.io_kind());
+    assert!(err.source().is_none());
+    assert_ne!(err, AccessTerminalError::new(AccessTerminalErrorKind::BrokenPipe, "meltdown"));
+    assert_eq!(err, AccessTerminalError::from("meltdown"));
+    assert_eq!(err, AccessTerminalError::from(String::from("meltdown")));
+}
 
 #[test]
 fn read_from_str_valid() {
@@ -36,10 +61,10 @@ fn read_from_str_invalid_corrected() {
 #[test]
 fn read_from_str_error() {
     let mut mock = Mock::default()
-        .on_read_line(|| Err(AccessTerminalError("invalid input".into())));
+        .on_read_line(|| Err(AccessTerminalError::other("invalid input")));
 
     let result = mock.read_from_str::<f64>(">>> ");
-    assert_eq!(AccessTerminalError("invalid input".into()), result.unwrap_err());
+    assert_eq!(AccessTerminalError::other("invalid input"), result.unwrap_err());
 
     assert_eq!(&[
         Invocation::Print(">>> ".into(), Ok(())),
@@ -47,6 +72,277 @@ fn read_from_str_error() {
     ], mock.invocations());
 }
 
+#[test]
+fn confirm_answered() {
+    let mut mock = Mock::default()
+        .on_read_line(mock::lines(&["Y", "no", "yes", "n"]));
+
+    assert!(mock.confirm("Proceed?", None).unwrap());
+    assert!(!mock.confirm("Proceed?", None).unwrap());
+    assert!(mock.confirm("Proceed?", Some(false)).unwrap());
+    assert!(!mock.confirm("Proceed?", Some(true)).unwrap());
+
+    assert_eq!(&[
+        Invocation::Print("Proceed? [y/n] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("Y".into())),
+        Invocation::Print("Proceed? [y/n] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("no".into())),
+        Invocation::Print("Proceed? [y/N] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("yes".into())),
+        Invocation::Print("Proceed? [Y/n] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("n".into())),
+    ], mock.invocations());
+}
+
+#[test]
+fn confirm_default() {
+    let mut mock = Mock::default()
+        .on_read_line(mock::lines(&["", " "]));
+
+    assert!(mock.confirm("Proceed?", Some(true)).unwrap());
+    assert!(!mock.confirm("Proceed?", Some(false)).unwrap());
+}
+
+#[test]
+fn confirm_invalid_corrected() {
+    let mut mock = Mock::default()
+        .on_read_line(mock::lines(&["maybe", "", "y"]));
+
+    assert!(mock.confirm("Proceed?", None).unwrap());
+
+    assert_eq!(&[
+        Invocation::Print("Proceed? [y/n] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("maybe".into())),
+        Invocation::Print("Invalid input: expected 'y' or 'n'.\n".into(), Ok(())),
+        Invocation::Print("Proceed? [y/n] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("".into())),
+        Invocation::Print("Invalid input: an answer is required.\n".into(), Ok(())),
+        Invocation::Print("Proceed? [y/n] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("y".into())),
+    ], mock.invocations());
+}
+
+#[test]
+fn select_valid() {
+    let mut mock = Mock::default()
+        .on_read_line(mock::lines(&["2"]));
+
+    let items = ["apple", "banana", "cherry"];
+    assert_eq!(&"banana", mock.select("Pick a fruit", &items).unwrap());
+
+    assert_eq!(&[
+        Invocation::Print("  1) apple\n".into(), Ok(())),
+        Invocation::Print("  2) banana\n".into(), Ok(())),
+        Invocation::Print("  3) cherry\n".into(), Ok(())),
+        Invocation::Print("Pick a fruit [1-3] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("2".into())),
+    ], mock.invocations());
+}
+
+#[test]
+fn select_invalid_corrected() {
+    let mut mock = Mock::default()
+        .on_read_line(mock::lines(&["0", "x", "3"]));
+
+    let items = ["apple", "banana", "cherry"];
+    assert_eq!(&"cherry", mock.select("Pick a fruit", &items).unwrap());
+
+    assert_eq!(&[
+        Invocation::Print("Pick a fruit [1-3] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("0".into())),
+        Invocation::Print("Invalid input: expected a number between 1 and 3, got '0'.\n".into(), Ok(())),
+        Invocation::Print("Pick a fruit [1-3] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("x".into())),
+        Invocation::Print("Invalid input: expected a number between 1 and 3, got 'x'.\n".into(), Ok(())),
+        Invocation::Print("Pick a fruit [1-3] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("3".into())),
+    ], &mock.invocations()[3..]);
+}
+
+#[test]
+fn select_aligns_numbers() {
+    let mut mock = Mock::default()
+        .on_read_line(mock::lines(&["10"]));
+
+    let items = (1..=10).map(|i| i * 100).collect::<Vec<_>>();
+    assert_eq!(&1000, mock.select("Pick", &items).unwrap());
+    assert_eq!("   1) 100\n", mock.invocations()[0].print().unwrap().0);
+    assert_eq!("  10) 1000\n", mock.invocations()[9].print().unwrap().0);
+}
+
+#[test]
+#[should_panic(expected = "no items to select from")]
+fn select_empty() {
+    let mut mock = Mock::default();
+    let _ = mock.select::<&str>("Pick", &[]);
+}
+
+#[test]
+fn select_many_valid() {
+    let mut mock = Mock::default()
+        .on_read_line(mock::lines(&["4, 1-2 2", ""]));
+
+    let items = ["apple", "banana", "cherry", "date"];
+    assert_eq!(vec![&"apple", &"banana", &"date"], mock.select_many("Pick fruits", &items).unwrap());
+    assert!(mock.select_many("Pick fruits", &items).unwrap().is_empty());
+
+    assert_eq!(&[
+        Invocation::Print("  1) apple\n".into(), Ok(())),
+        Invocation::Print("  2) banana\n".into(), Ok(())),
+        Invocation::Print("  3) cherry\n".into(), Ok(())),
+        Invocation::Print("  4) date\n".into(), Ok(())),
+        Invocation::Print("Pick fruits [e.g., 1,2-4] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("4, 1-2 2".into())),
+    ], &mock.invocations()[..6]);
+}
+
+#[test]
+fn select_many_invalid_corrected() {
+    let mut mock = Mock::default()
+        .on_read_line(mock::lines(&["3-1", "1-5", "2-3"]));
+
+    let items = ["apple", "banana", "cherry", "date"];
+    assert_eq!(vec![&"banana", &"cherry"], mock.select_many("Pick fruits", &items).unwrap());
+
+    assert_eq!(&[
+        Invocation::Print("Pick fruits [e.g., 1,2-4] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("3-1".into())),
+        Invocation::Print("Invalid input: invalid range '3-1'.\n".into(), Ok(())),
+        Invocation::Print("Pick fruits [e.g., 1,2-4] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("1-5".into())),
+        Invocation::Print("Invalid input: expected a number between 1 and 4, got '5'.\n".into(), Ok(())),
+        Invocation::Print("Pick fruits [e.g., 1,2-4] ".into(), Ok(())),
+        Invocation::ReadLine(Ok("2-3".into())),
+    ], &mock.invocations()[4..]);
+}
+
+#[test]
+#[should_panic(expected = "no items to select from")]
+fn select_many_empty() {
+    let mut mock = Mock::default();
+    let _ = mock.select_many::<&str>("Pick", &[]);
+}
+
+#[test]
+fn select_single_item_hints() {
+    let mut mock = Mock::default()
+        .on_read_line(mock::lines(&["1", "1"]));
+
+    let items = ["apple"];
+    assert_eq!(&"apple", mock.select("Pick a fruit", &items).unwrap());
+    assert_eq!(vec![&"apple"], mock.select_many("Pick fruits", &items).unwrap());
+    assert_eq!("Pick a fruit [1] ", mock.invocations()[1].print().unwrap().0);
+    assert_eq!("Pick fruits [e.g., 1] ", mock.invocations()[4].print().unwrap().0);
+}
+
+#[test]
+fn select_keys() {
+    let mut mock = Mock::default()
+        .on_read_key(mock::keys(&["\x1b[B", "j", "j", "\x1b[A", "x", "\r"]));
+
+    let items = ["apple", "banana", "cherry"];
+    assert_eq!(&"banana", mock.select("Pick a fruit", &items).unwrap());
+
+    let invocations = mock.invocations();
+    assert_eq!(Invocation::Print("Pick a fruit (up/down: move, enter: select)\n".into(), Ok(())), invocations[0]);
+    assert_eq!(Invocation::Print("\r\x1b[2K> apple\n\r\x1b[2K  banana\n\r\x1b[2K  cherry\n".into(), Ok(())), invocations[1]);
+    assert_eq!(Invocation::ReadKey(Ok(b"\x1b[B".to_vec())), invocations[2]);
+    assert_eq!(Invocation::Print("\x1b[3A\r\x1b[2K  apple\n\r\x1b[2K> banana\n\r\x1b[2K  cherry\n".into(), Ok(())), invocations[3]);
+    assert_eq!(Invocation::Print("\x1b[3A\r\x1b[2K  apple\n\r\x1b[2K  banana\n\r\x1b[2K> cherry\n".into(), Ok(())), invocations[7]);
+    assert_eq!(&[
+        Invocation::Print("\x1b[4A\r\x1b[J".into(), Ok(())),
+        Invocation::Print("Pick a fruit banana\n".into(), Ok(())),
+    ], &invocations[invocations.len() - 2..]);
+}
+
+#[test]
+fn select_keys_highlights_cursor() {
+    let mut mock = Mock::default()
+        .with_ansi(true)
+        .on_read_key(mock::keys(&["\r"]));
+
+    assert_eq!(&"apple", mock.select("Pick a fruit", &["apple", "banana"]).unwrap());
+    assert_eq!(
+        format!("\r\x1b[2K{}\n\r\x1b[2K  banana\n", Style::default().bold().paint("> apple")),
+        mock.invocations()[1].print().unwrap().0
+    );
+}
+
+#[test]
+fn select_keys_interrupted() {
+    let mut mock = Mock::default()
+        .on_read_key(mock::keys(&["j", "\x03"]));
+
+    let err = mock.select("Pick a fruit", &["apple", "banana"]).unwrap_err();
+    assert_eq!(AccessTerminalErrorKind::Interrupted, err.kind());
+    assert_eq!(
+        Some(&Invocation::Print("\x1b[3A\r\x1b[J".into(), Ok(()))),
+        mock.invocations().last()
+    );
+}
+
+#[test]
+fn select_keys_end_of_input() {
+    let mut mock = Mock::default()
+        .on_read_key(mock::keys(&["j"]));
+
+    let err = mock.select("Pick a fruit", &["apple", "banana"]).unwrap_err();
+    assert_eq!(AccessTerminalErrorKind::EndOfInput, err.kind());
+}
+
+#[test]
+fn select_many_keys() {
+    let mut mock = Mock::default()
+        .on_read_key(mock::keys(&["G", " ", "g", " ", "j", " ", " ", "\r", "\n"]));
+
+    let items = ["apple", "banana", "cherry", "date"];
+    assert_eq!(vec![&"apple", &"date"], mock.select_many("Pick fruits", &items).unwrap());
+    assert!(mock.select_many("Pick fruits", &items).unwrap().is_empty());
+
+    let invocations = mock.invocations();
+    assert_eq!(Invocation::Print("Pick fruits (up/down: move, space: toggle, enter: confirm)\n".into(), Ok(())), invocations[0]);
+    assert_eq!(
+        Invocation::Print("\x1b[4A\r\x1b[2K  [x] apple\n\r\x1b[2K> [ ] banana\n\r\x1b[2K  [ ] cherry\n\r\x1b[2K  [x] date\n".into(), Ok(())),
+        invocations[15]
+    );
+    assert_eq!(Invocation::Print("Pick fruits apple, date\n".into(), Ok(())), invocations[18]);
+    assert_eq!(Some(&Invocation::Print("Pick fruits\n".into(), Ok(()))), invocations.last());
+}
+
+#[test]
+fn select_keys_truncates_items() {
+    let mut mock = Mock::default()
+        .with_size(TerminalSize::new(10, 24))
+        .on_read_key(mock::keys(&["\r"]));
+
+    assert_eq!(&"pineapple", mock.select("Pick", &["pineapple"]).unwrap());
+    assert_eq!("\r\x1b[2K> pineappl\n", mock.invocations()[1].print().unwrap().0);
+    assert_eq!("Pick pineappl\n", mock.invocations()[4].print().unwrap().0);
+
+    let mut mock = Mock::default()
+        .with_size(TerminalSize::new(10, 24))
+        .on_read_key(mock::keys(&["\r"]));
+    assert!(mock.select_many("Pick", &["pineapple"]).unwrap().is_empty());
+    assert_eq!("\r\x1b[2K> [ ] pine\n", mock.invocations()[1].print().unwrap().0);
+}
+
+#[test]
+fn read_secret_default() {
+    struct LineTerminal;
+
+    impl Terminal for LineTerminal {
+        fn print(&mut self, _: &str) -> Result<(), AccessTerminalError> {
+            unimplemented!()
+        }
+
+        fn read_line(&mut self) -> Result<String, AccessTerminalError> {
+            Ok("hunter2\n".into())
+        }
+    }
+
+    assert_eq!("hunter2", LineTerminal.read_secret().unwrap().expose());
+}
+
 #[test]
 fn read_from_str_default_empty() {
     let mut mock = Mock::default()
@@ -64,13 +360,88 @@ fn read_from_str_default_empty() {
 #[test]
 fn read_from_str_default_valid() {
     let mut mock = Mock::default()
-        .on_read_line(mock::lines(&["3.14"]));
+        .on_read_line(mock::lines(&["2.5"]));
 
     let result = mock.read_from_str_default::<f64>(">>> ");
-    assert_eq!(3.14, result.unwrap());
+    assert_eq!(2.5, result.unwrap());
 
     assert_eq!(&[
         Invocation::Print(">>> ".into(), Ok(())),
-        Invocation::ReadLine(Ok("3.14".into())),
+        Invocation::ReadLine(Ok("2.5".into())),
+    ], mock.invocations());
+}
+
+#[test]
+fn print_styled_plain() {
+    let mut mock = Mock::default();
+    assert!(!mock.supports_ansi());
+    mock.print_styled("bold", Style::default().bold()).unwrap();
+    mock.print_line_styled("bold line", Style::default().bold()).unwrap();
+    mock.print_info("info").unwrap();
+    mock.print_warning("warning").unwrap();
+    mock.print_error("error").unwrap();
+    mock.print_success("success").unwrap();
+
+    assert_eq!(&[
+        Invocation::Print("bold".into(), Ok(())),
+        Invocation::Print("bold line\n".into(), Ok(())),
+        Invocation::Print("info\n".into(), Ok(())),
+        Invocation::Print("warning\n".into(), Ok(())),
+        Invocation::Print("error\n".into(), Ok(())),
+        Invocation::Print("success\n".into(), Ok(())),
+    ], mock.invocations());
+}
+
+#[test]
+fn print_styled_ansi() {
+    let mut mock = Mock::default().with_ansi(true);
+    assert!(mock.supports_ansi());
+    mock.print_styled("bold", Style::default().bold()).unwrap();
+    mock.print_line_styled("bold line", Style::default().bold()).unwrap();
+    mock.print_level(Level::Error, "error").unwrap();
+
+    assert_eq!(&[
+        Invocation::Print("\x1b[1mbold\x1b[0m".into(), Ok(())),
+        Invocation::Print("\x1b[1mbold line\x1b[0m\n".into(), Ok(())),
+        Invocation::Print("\x1b[1;31merror\x1b[0m\n".into(), Ok(())),
     ], mock.invocations());
-}
\ No newline at end of file
+}
+
+#[test]
+fn print_table_formats() {
+    let table = Table::new(vec![Column::new("Name"), Column::new("Description")])
+        .with_row(["alpha", "the first letter of the Greek alphabet"]);
+
+    let mut mock = Mock::default().with_size(TerminalSize::new(30, 24));
+    mock.print_table(&table).unwrap();
+    assert_eq!(table.render(OutputFormat::Text, TerminalSize::new(30, 24), false), mock.output());
+    assert!(mock.output().lines().all(|line| line.chars().count() == 30));
+
+    let mut mock = Mock::default().with_output_format(OutputFormat::Csv);
+    mock.print_table(&table).unwrap();
+    assert_eq!("Name,Description\nalpha,the first letter of the Greek alphabet\n", mock.output());
+
+    let mut mock = Mock::default().with_output_format(OutputFormat::Json);
+    mock.print_table(&table).unwrap();
+    assert_eq!("{\"Name\":\"alpha\",\"Description\":\"the first letter of the Greek alphabet\"}\n", mock.output());
+}
+
+#[test]
+fn emit_formats() {
+    let value = Value::object([("id", 7.into()), ("name", "seven".into())]);
+
+    let mut mock = Mock::default();
+    mock.emit(&value).unwrap();
+    mock.emit(&Value::Object(vec![])).unwrap();
+    assert_eq!("id: 7\nname: seven\n", mock.output());
+
+    let mut mock = Mock::default().with_output_format(OutputFormat::Csv);
+    mock.emit(&value).unwrap();
+    mock.emit(&Value::Object(vec![])).unwrap();
+    assert_eq!("7,seven\n", mock.output());
+
+    let mut mock = Mock::default().with_output_format(OutputFormat::Json);
+    mock.emit(&value).unwrap();
+    mock.emit(&Value::Object(vec![])).unwrap();
+    assert_eq!("{\"id\":7,\"name\":\"seven\"}\n{}\n", mock.output());
+}
@@ -0,0 +1,190 @@
+//! Low-level control of the terminal attached to `stdin` and `stdout`: echo, key presses and
+//! window dimensions. Only Unix-like systems are supported; elsewhere, the streams are not deemed
+//! terminals, echo and raw mode are left unchanged, and the dimensions are unknown.
+
+use crate::terminal::{AccessTerminalError, TerminalSize};
+use std::env;
+use std::io::{stdin, BufRead};
+
+/// Are both `stdin` and `stdout` terminals that are capable of cursor control (i.e., `TERM` is not
+/// `dumb`)?
+pub(crate) fn is_interactive() -> bool {
+    stdin_is_terminal() && stdout_controls_cursor()
+}
+
+/// Is `stdout` a terminal that is capable of cursor control (i.e., `TERM` is not `dumb`)?
+pub(crate) fn stdout_controls_cursor() -> bool {
+    stdout_is_terminal() && env::var_os("TERM").map_or(true, |term| term != "dumb")
+}
+
+/// Is `stdin` a terminal?
+pub(crate) fn stdin_is_terminal() -> bool {
+    #[cfg(unix)]
+    // SAFETY: `isatty` only inspects the descriptor
+    return unsafe { libc::isatty(libc::STDIN_FILENO) } == 1;
+
+    #[cfg(not(unix))]
+    false
+}
+
+/// Is `stdout` a terminal?
+pub(crate) fn stdout_is_terminal() -> bool {
+    #[cfg(unix)]
+    // SAFETY: `isatty` only inspects the descriptor
+    return unsafe { libc::isatty(libc::STDOUT_FILENO) } == 1;
+
+    #[cfg(not(unix))]
+    false
+}
+
+/// Invokes the given closure with echo disabled on `stdin`, if `stdin` is a terminal. Line breaks
+/// are still echoed, so that the cursor moves past the (invisible) input.
+pub(crate) fn without_echo<R>(
+    f: impl FnOnce() -> Result<R, AccessTerminalError>,
+) -> Result<R, AccessTerminalError> {
+    #[cfg(unix)]
+    return with_termios(
+        |termios| {
+            termios.c_lflag &= !libc::ECHO;
+            termios.c_lflag |= libc::ECHONL;
+        },
+        f,
+    );
+
+    #[cfg(not(unix))]
+    f()
+}
+
+/// Invokes the given closure with `stdin` in raw mode, if `stdin` is a terminal: input is delivered
+/// one key press at a time, without echo, and Ctrl-C is delivered as a byte rather than a signal.
+pub(crate) fn with_raw_mode<R>(
+    f: impl FnOnce() -> Result<R, AccessTerminalError>,
+) -> Result<R, AccessTerminalError> {
+    #[cfg(unix)]
+    return with_termios(
+        |termios| {
+            termios.c_lflag &= !(libc::ECHO | libc::ICANON | libc::ISIG);
+            termios.c_cc[libc::VMIN] = 1;
+            termios.c_cc[libc::VTIME] = 0;
+        },
+        f,
+    );
+
+    #[cfg(not(unix))]
+    f()
+}
+
+/// Reads a single key press from `stdin` in raw mode, returning [`AccessTerminalError::end_of_input`]
+/// once `stdin` is exhausted. The key is taken from the buffer of [`std::io::Stdin`], so that input
+/// buffered by an earlier read is not skipped; see [`key_len`].
+pub(crate) fn read_key() -> Result<Vec<u8>, AccessTerminalError> {
+    with_raw_mode(|| {
+        let mut stdin = stdin().lock();
+        let buf = stdin.fill_buf()?;
+        if buf.is_empty() {
+            return Err(AccessTerminalError::end_of_input());
+        }
+        let key = buf[..key_len(buf)].to_vec();
+        stdin.consume(key.len());
+        Ok(key)
+    })
+}
+
+/// The length of the first key press in the given bytes: an escape sequence (`ESC [`, followed by
+/// parameters and a final byte, or `ESC O`, followed by a single byte), or a single UTF-8 encoded
+/// character.
+pub(crate) fn key_len(bytes: &[u8]) -> usize {
+    let len = match bytes {
+        [] => 0,
+        [0x1b, b'[', rest @ ..] => rest
+            .iter()
+            .position(|byte| (0x40..=0x7e).contains(byte))
+            .map_or(bytes.len(), |index| index + 3),
+        [0x1b, b'O', _, ..] | [0xe0..=0xef, ..] => 3,
+        [0xc0..=0xdf, ..] => 2,
+        [0xf0..=0xf7, ..] => 4,
+        [_, ..] => 1,
+    };
+    len.min(bytes.len())
+}
+
+/// The dimensions of the terminal attached to `stdout`, or [`None`] if they cannot be determined.
+/// A dimension that is not reported by the terminal is taken from the `COLUMNS` or `LINES`
+/// environment variable, if set.
+pub(crate) fn size() -> Option<TerminalSize> {
+    let (columns, rows) = window_size().unwrap_or_default();
+    let columns = (columns > 0).then_some(columns).or_else(|| env_dimension("COLUMNS"))?;
+    let rows = (rows > 0).then_some(rows).or_else(|| env_dimension("LINES"))?;
+    Some(TerminalSize { columns, rows })
+}
+
+/// Parses a positive dimension from the given environment variable.
+fn env_dimension(key: &str) -> Option<usize> {
+    env::var(key).ok()?.trim().parse().ok().filter(|&value| value > 0)
+}
+
+/// The (columns, rows) reported by the terminal attached to `stdout`.
+fn window_size() -> Option<(usize, usize)> {
+    #[cfg(unix)]
+    {
+        let mut size = std::mem::MaybeUninit::<libc::winsize>::uninit();
+        // SAFETY: `TIOCGWINSZ` initialises the struct on success, which is checked before use
+        let size = unsafe {
+            if libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, size.as_mut_ptr()) != 0 {
+                return None;
+            }
+            size.assume_init()
+        };
+        Some((usize::from(size.ws_col), usize::from(size.ws_row)))
+    }
+
+    #[cfg(not(unix))]
+    None
+}
+
+/// Invokes the given closure with the terminal settings of `stdin` modified by `adjust`, if `stdin`
+/// is a terminal. The original settings are restored on return, even if the closure panics.
+#[cfg(unix)]
+fn with_termios<R>(
+    adjust: impl FnOnce(&mut libc::termios),
+    f: impl FnOnce() -> Result<R, AccessTerminalError>,
+) -> Result<R, AccessTerminalError> {
+    use std::mem::MaybeUninit;
+
+    /// Restores the original terminal settings when dropped.
+    struct Restore(libc::termios);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            // SAFETY: the settings were obtained from `tcgetattr` on the same descriptor
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.0);
+            }
+        }
+    }
+
+    if !stdin_is_terminal() {
+        return f();
+    }
+
+    let mut original = MaybeUninit::<libc::termios>::uninit();
+    // SAFETY: `tcgetattr` initialises the struct on success, which is checked before use
+    let original = unsafe {
+        if libc::tcgetattr(libc::STDIN_FILENO, original.as_mut_ptr()) != 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+        original.assume_init()
+    };
+
+    let mut adjusted = original;
+    adjust(&mut adjusted);
+    let _restore = Restore(original);
+    // SAFETY: `adjusted` is a valid, initialised copy of the original settings
+    if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &adjusted) } != 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    f()
+}
+
+#[cfg(test)]
+mod tests;
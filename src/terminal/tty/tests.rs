@@ -0,0 +1,18 @@
+// $coverage:ignore-start
+
+use crate::terminal::tty::key_len;
+
+#[test]
+fn key_lengths() {
+    assert_eq!(0, key_len(b""));
+    assert_eq!(1, key_len(b"q"));
+    assert_eq!(1, key_len(b"jk"));
+    assert_eq!(1, key_len(b"\r"));
+    assert_eq!(3, key_len(b"\x1b[A\x1b[B"));
+    assert_eq!(4, key_len(b"\x1b[6~"));
+    assert_eq!(3, key_len(b"\x1bOB"));
+    assert_eq!(2, key_len(b"\x1b["));
+    assert_eq!(1, key_len(b"\x1b"));
+    assert_eq!(2, key_len("éa".as_bytes()));
+    assert_eq!(4, key_len("🦀".as_bytes()));
+}